@@ -13,6 +13,71 @@ pub struct PlacedElement<T> {
     pub element: T,
     /// The location of the element on the 2D plane.
     pub position: Rect,
+    /// The location of the content of the element on the 2D plane, inside of `position`.
+    pub content_position: Rect,
+    /// The part of the content that is cut off before `content_position`, because it lies before
+    /// the origin of the plane.
+    pub content_offset: XY<usize>,
+    /// The stacking order of the element. Elements with a higher z-index are on top of elements
+    /// with a lower z-index.
+    pub z_index: i32,
+    /// The line of the layout the element is in, counting from the top or left side. Elements
+    /// that are positioned outside of the lines are in none.
+    pub line: Option<usize>,
+}
+
+impl<T> PlacedElement<T> {
+    /// Return the full size of the content of the element, including the part that is cut off.
+    pub fn size(&self) -> XY<usize> {
+        self.content_position.size() + self.content_offset
+    }
+}
+
+/// A window on the 2D plane that may start before the origin of the plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    /// The top left corner of the window, which can be negative.
+    pub top_left: XY<isize>,
+    /// The size of the window.
+    pub size: XY<usize>,
+}
+
+impl Window {
+    /// Create a new window from its top left corner and its size.
+    pub fn from_size(top_left: impl Into<XY<isize>>, size: impl Into<XY<usize>>) -> Self {
+        Window {
+            top_left: top_left.into(),
+            size: size.into(),
+        }
+    }
+
+    /// Move the window by `offset`.
+    pub fn offset(self, offset: impl Into<XY<isize>>) -> Self {
+        let offset = offset.into();
+        Window {
+            top_left: self.top_left + offset,
+            size: self.size,
+        }
+    }
+
+    /// Change the size of the window, keeping its top left corner.
+    pub fn resized(self, size: impl Into<XY<usize>>) -> Self {
+        Window {
+            top_left: self.top_left,
+            size: size.into(),
+        }
+    }
+
+    /// Clip the window to the positive side of the plane. Returns the visible part of the window
+    /// and the size of the part that was cut off.
+    pub fn clip(self) -> (Rect, XY<usize>) {
+        let content_offset = self.top_left.zip_map(self.size, |coordinate, size| {
+            coordinate.min(0).unsigned_abs().min(size)
+        });
+        let top_left = self.top_left.map(|coordinate| coordinate.max(0) as usize);
+        let size = self.size.saturating_sub(content_offset);
+        (Rect::from_size(top_left, size), content_offset)
+    }
 }
 
 /// A concrete layout of elements.
@@ -22,12 +87,22 @@ pub struct Layout<T> {
 }
 
 impl<T> Layout<T> {
-    /// Return the item at `position`, or None if there is no item.
+    /// Return the topmost item at `position`, or None if there is no item.
     pub fn element_at(&self, position: XY<usize>) -> Option<&PlacedElement<T>> {
-        self.iter()
+        self.stacking_order()
+            .into_iter()
+            .rev()
             .find(|&element| element.position.contains(position))
     }
 
+    /// Return the items of this layout from the bottom to the top. Items with the same z-index keep
+    /// their order in the layout.
+    pub fn stacking_order(&self) -> Vec<&PlacedElement<T>> {
+        let mut elements = self.iter().collect::<Vec<_>>();
+        elements.sort_by_key(|element| element.z_index);
+        elements
+    }
+
     /// Return an iterator over the items of this layout.
     pub fn iter(&self) -> Iter<'_, PlacedElement<T>> {
         self.into_iter()
@@ -18,17 +18,27 @@ pub mod prelude;
 
 use std::{
     cell::RefCell,
+    cmp::Reverse,
     fmt::Display,
     rc::{Rc, Weak},
 };
 
-use cursive_core::{event::EventResult, view::IntoBoxedView, Rect, Vec2, View, XY};
-use layout::{Layout, PlacedElement};
+use cursive_core::{
+    direction::{Absolute, Direction, Relative},
+    event::{Event, EventResult, Key},
+    view::IntoBoxedView,
+    Rect, Vec2, View, XY,
+};
+use layout::{Layout, PlacedElement, Window};
+
+/// The height of a terminal cell divided by its width. Used to keep the aspect ratio of items as
+/// it appears on the screen.
+const CELL_ASPECT_RATIO: f64 = 2.0;
 
 /// A container that can be used to display a list of items in a flexible way.
 pub struct Flexbox {
-    /// The content of the flexbox. Unlike some flexboxes, order is always dictated by the order of
-    /// the items in `content`. There is no way to overwrite this.
+    /// The content of the flexbox. Items are layed out in the order of `content`, unless this is
+    /// overwritten with the order of the items.
     content: Vec<Rc<RefCell<FlexItem>>>,
     /// Options to alter the behavior.
     options: FlexBoxOptions,
@@ -59,7 +69,42 @@ pub struct FlexItem {
     /// A relative amount of free space in the main axis this item is in that should be given to
     /// this item. The amount is relative as it's proportional to the total amount of free space
     /// requested by all items in the same main axis.
-    flex_grow: u8,
+    flex_grow: f64,
+    /// A relative amount of overflowing space in the main axis this item is in that should be
+    /// taken away from this item. The amount is relative as it's proportional to the flex-shrink
+    /// of all the items in the same main axis, each scaled by the size of their item.
+    flex_shrink: f64,
+    /// The initial size of this item along the main axis, before free space is distributed.
+    flex_basis: FlexBasis,
+    /// The minimum size of this item along each axis.
+    min_size: XY<Option<Length>>,
+    /// The maximum size of this item along each axis.
+    max_size: XY<Option<Length>>,
+    /// How to place this item on the cross axis, overriding the align-items of the flexbox.
+    align_self: AlignSelf,
+    /// The position of this item in the layout. Items are layed out in ascending order, items with
+    /// the same order keep the order they have in the flexbox.
+    order: i32,
+    /// The space around this item.
+    margin: Sides<Length>,
+    /// The offset of the first baseline of this item from its cross-start side, used when the item
+    /// is aligned on its baseline. If not set, the baseline is synthesized from the view.
+    baseline: Option<usize>,
+    /// The preferred width of this item divided by its height, as it appears on the screen. The
+    /// size along the cross axis is derived from the size along the main axis.
+    aspect_ratio: Option<f64>,
+    /// Whether this item is layed out with the other items, or positioned relative to the edges of
+    /// the flexbox.
+    position: Position,
+    /// The offsets of an absolutely positioned item from the edges of the flexbox.
+    inset: Sides<Length>,
+    /// The stacking order of this item. Items with a higher z-index are drawn on top of items with
+    /// a lower z-index.
+    z_index: i32,
+    /// Whether this item is shown, and whether it takes up space when it isn't.
+    visibility: Visibility,
+    /// How the view is placed inside of the space assigned to this item, along each axis.
+    placement: XY<Placement>,
 }
 
 /// Options that can alter the behavior of a flexbox.
@@ -74,29 +119,85 @@ struct FlexBoxOptions {
     item_alignment: AlignItems,
     /// How to place the main axes in the container.
     axes_alignment: AlignContent,
-    /// Gap between items on the main axis. The gap doesn't get added to the sides.
-    main_axis_gap: u32,
+    /// Gap between items on the main axis. The gap doesn't get added to the sides, unless
+    /// `edge_gaps` is set.
+    main_axis_gap: Length,
     /// Gap between the main axes.
-    cross_axis_gap: u32,
+    cross_axis_gap: Length,
     /// Wrapping behavior of the main axes.
     wrap: FlexWrap,
+    /// Space between the sides of the container and its items.
+    padding: Sides<Length>,
+    /// Whether the gaps are added to the sides of the container as well, on top of the padding.
+    edge_gaps: bool,
+    /// What to do when the items overflow the main axis they're justified on.
+    justify_overflow: OverflowAlignment,
+    /// What to do when the items or main axes overflow the cross axis they're aligned on.
+    align_overflow: OverflowAlignment,
+    /// How much space the flexbox requests from its parent.
+    sizing: SizingPolicy,
+    /// Where the cells go that are left over when space is divided over items or main axes.
+    rounding: RoundingPolicy,
+    /// Where the baseline of items without an explicit baseline is.
+    baseline_fallback: BaselineFallback,
+}
+
+impl FlexBoxOptions {
+    /// Return the space between the sides of a container with `size` and its items. This is the
+    /// padding, together with the gaps if they are added to the sides. Percentages of the padding
+    /// are relative to `size`, those of the gaps to the size inside of the padding.
+    fn edge_space(&self, size: XY<usize>) -> Sides<usize> {
+        let padding = Sides::new(
+            self.padding.top.resolve(size.y).cells(),
+            self.padding.right.resolve(size.x).cells(),
+            self.padding.bottom.resolve(size.y).cells(),
+            self.padding.left.resolve(size.x).cells(),
+        );
+        if !self.edge_gaps {
+            return padding;
+        }
+        let inner_size =
+            size.saturating_sub((padding.left + padding.right, padding.top + padding.bottom));
+        let (horizontal_gap, vertical_gap) = match self.direction {
+            FlexDirection::Row | FlexDirection::RowReverse => {
+                (self.main_axis_gap, self.cross_axis_gap)
+            },
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                (self.cross_axis_gap, self.main_axis_gap)
+            },
+        };
+        let horizontal_gap = horizontal_gap.resolve(inner_size.x).cells();
+        let vertical_gap = vertical_gap.resolve(inner_size.y).cells();
+        Sides::new(
+            padding.top + vertical_gap,
+            padding.right + horizontal_gap,
+            padding.bottom + vertical_gap,
+            padding.left + horizontal_gap,
+        )
+    }
 }
 
 // https://developer.mozilla.org/en-US/docs/Web/CSS/flex-direction
 // https://w3c.github.io/csswg-drafts/css-flexbox/#flex-direction-property
 /// Direction of a flex container's main axis.
-#[non_exhaustive] // TODO: Implement RowReverse and ColumnReverse!
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FlexDirection {
     /// Flex items are layed out in a row.
     #[default]
     Row,
-    // /// Flex items are layed out in a row, in reverse order.
-    // RowReverse,
+    /// Flex items are layed out in a row, in reverse order.
+    RowReverse,
     /// Flex items are layed out in a column.
     Column,
-    // /// Flex items are layed out in a column, in reverse order.
-    // ColumnReverse,
+    /// Flex items are layed out in a column, in reverse order.
+    ColumnReverse,
+}
+
+impl FlexDirection {
+    /// Return whether the main-start and main-end of this direction are swapped.
+    fn is_reverse(&self) -> bool {
+        matches!(self, Self::RowReverse | Self::ColumnReverse)
+    }
 }
 
 impl Display for FlexDirection {
@@ -106,7 +207,9 @@ impl Display for FlexDirection {
             "{}",
             match self {
                 Self::Row => "row",
+                Self::RowReverse => "row-reverse",
                 Self::Column => "column",
+                Self::ColumnReverse => "column-reverse",
             }
         )
     }
@@ -159,6 +262,38 @@ pub enum JustifyContent {
     SpaceAround,
     /// Flex items are packed with equal space between all items (including the sides).
     SpaceEvenly, // Included although not in w3c specification.
+    /// Flex items are packed against the start of the container, regardless of the direction.
+    Start,
+    /// Flex items are packed against the end of the container, regardless of the direction.
+    End,
+    /// Flex items are packed against the left side of the container. Behaves as
+    /// [JustifyContent::Start] in a column.
+    Left,
+    /// Flex items are packed against the right side of the container. Behaves as
+    /// [JustifyContent::Start] in a column.
+    Right,
+}
+
+impl JustifyContent {
+    /// Return the justification relative to main-start for a flexbox with `direction`.
+    fn resolve(self, direction: FlexDirection) -> JustifyContent {
+        let (start, end) = if direction.is_reverse() {
+            (Self::FlexEnd, Self::FlexStart)
+        } else {
+            (Self::FlexStart, Self::FlexEnd)
+        };
+        match self {
+            Self::Start => start,
+            Self::End => end,
+            // Left is the same as start for a row, and columns have no horizontal main axis.
+            Self::Left => start,
+            Self::Right => match direction {
+                FlexDirection::Row | FlexDirection::RowReverse => end,
+                FlexDirection::Column | FlexDirection::ColumnReverse => start,
+            },
+            justification => justification,
+        }
+    }
 }
 
 impl Display for JustifyContent {
@@ -173,6 +308,35 @@ impl Display for JustifyContent {
                 Self::SpaceBetween => "space-between",
                 Self::SpaceAround => "space-around",
                 Self::SpaceEvenly => "space-evenly",
+                Self::Start => "start",
+                Self::End => "end",
+                Self::Left => "left",
+                Self::Right => "right",
+            }
+        )
+    }
+}
+
+// https://developer.mozilla.org/en-US/docs/Web/CSS/justify-content#safe
+// https://w3c.github.io/csswg-drafts/css-align/#overflow-values
+/// What to do with items that overflow the container because of their alignment.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverflowAlignment {
+    /// Honor the alignment, even if items overflow the start of the container and can't be shown.
+    #[default]
+    Unsafe,
+    /// Align overflowing items to the start of the container, so only their end gets cut off.
+    Safe,
+}
+
+impl Display for OverflowAlignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Unsafe => "unsafe",
+                Self::Safe => "safe",
             }
         )
     }
@@ -180,8 +344,8 @@ impl Display for JustifyContent {
 
 // https://developer.mozilla.org/en-US/docs/Web/CSS/align-items
 // https://w3c.github.io/csswg-drafts/css-flexbox/#align-items-property
-// Baseline isn't included as Cursive doesn't support it, and it makes little sense in a TUI.
 /// Alignment of items in a flexbox along the cross axis.
+#[non_exhaustive] // Specification lists more options. Might be added later.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AlignItems {
     /// Align flex items at the start of the cross axis.
@@ -193,6 +357,10 @@ pub enum AlignItems {
     /// Stretch flex items to fill all the space along the cross axis.
     #[default] // Following w3c specification as there is no 'normal' option.
     Stretch,
+    /// Align the baselines of flex items, so their first lines of text line up. Behaves as
+    /// [AlignItems::FlexStart] in a column. Items without an explicit baseline use the
+    /// [BaselineFallback] of the flexbox, which synthesizes it from their bottom row by default.
+    Baseline,
 }
 
 impl Display for AlignItems {
@@ -205,6 +373,60 @@ impl Display for AlignItems {
                 Self::FlexEnd => "flex-end",
                 Self::Center => "center",
                 Self::Stretch => "stretch",
+                Self::Baseline => "baseline",
+            }
+        )
+    }
+}
+
+// https://developer.mozilla.org/en-US/docs/Web/CSS/align-self
+// https://w3c.github.io/csswg-drafts/css-flexbox/#propdef-align-self
+/// Alignment of a single item in a flexbox along the cross axis, overriding the align-items of the
+/// flexbox.
+#[non_exhaustive] // Specification lists more options. Might be added later.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlignSelf {
+    /// Use the align-items of the flexbox.
+    #[default]
+    Auto,
+    /// Align the flex item at the start of the cross axis.
+    FlexStart,
+    /// Align the flex item at the end of the cross axis.
+    FlexEnd,
+    /// Align the flex item at the center of the cross axis.
+    Center,
+    /// Stretch the flex item to fill all the space along the cross axis.
+    Stretch,
+    /// Align the baseline of the flex item with the other items aligned on their baseline.
+    Baseline,
+}
+
+impl AlignSelf {
+    /// Return the alignment to use for an item in a flexbox with `item_alignment` as align-items.
+    fn resolve(self, item_alignment: AlignItems) -> AlignItems {
+        match self {
+            Self::Auto => item_alignment,
+            Self::FlexStart => AlignItems::FlexStart,
+            Self::FlexEnd => AlignItems::FlexEnd,
+            Self::Center => AlignItems::Center,
+            Self::Stretch => AlignItems::Stretch,
+            Self::Baseline => AlignItems::Baseline,
+        }
+    }
+}
+
+impl Display for AlignSelf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Auto => "auto",
+                Self::FlexStart => "flex-start",
+                Self::FlexEnd => "flex-end",
+                Self::Center => "center",
+                Self::Stretch => "stretch",
+                Self::Baseline => "baseline",
             }
         )
     }
@@ -213,7 +435,7 @@ impl Display for AlignItems {
 // https://developer.mozilla.org/en-US/docs/Web/CSS/align-content
 // https://w3c.github.io/csswg-drafts/css-flexbox/#align-content-property
 /// Alignment of the main axes in a flexbox.
-#[non_exhaustive] // Might add space-evenly, even though not in w3c specification.
+#[non_exhaustive] // Specification lists more options. Might be added later.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AlignContent {
     /// Align content to the start of the container.
@@ -229,6 +451,8 @@ pub enum AlignContent {
     SpaceBetween,
     /// Align main axis with an equal of margin per axis.
     SpaceAround,
+    /// Align main axis with an equal amount of space between them and the sides.
+    SpaceEvenly, // Included although not in w3c specification.
 }
 
 impl Display for AlignContent {
@@ -243,6 +467,366 @@ impl Display for AlignContent {
                 Self::Stretch => "stretch",
                 Self::SpaceBetween => "space-between",
                 Self::SpaceAround => "space-around",
+                Self::SpaceEvenly => "space-evenly",
+            }
+        )
+    }
+}
+
+// https://developer.mozilla.org/en-US/docs/Web/CSS/flex-basis
+// https://w3c.github.io/csswg-drafts/css-flexbox/#flex-basis-property
+/// Initial main size of a flex item, before free space is distributed.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum FlexBasis {
+    /// Use the size of the content of the flex item. Flex items don't have a separate main size
+    /// property, so this is the same as [FlexBasis::Content].
+    #[default]
+    Auto,
+    /// Use the size of the content of the flex item.
+    Content,
+    /// A fixed amount of cells.
+    Cells(usize),
+    /// A percentage of the size of the container along the main axis.
+    Percent(f64),
+    /// Start from nothing, so all the space of an item is assigned by flex-grow.
+    Zero,
+}
+
+impl Display for FlexBasis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+            Self::Content => write!(f, "content"),
+            Self::Cells(cells) => write!(f, "{cells}"),
+            Self::Percent(percent) => write!(f, "{percent}%"),
+            Self::Zero => write!(f, "0"),
+        }
+    }
+}
+
+// https://developer.mozilla.org/en-US/docs/Web/CSS/length-percentage
+// https://w3c.github.io/csswg-drafts/css-flexbox/#auto-margins
+/// A length along one of the axes of a flexbox, used for gaps, padding, margins and size
+/// constraints.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length {
+    /// A fixed amount of cells.
+    Cells(usize),
+    /// A percentage of the size of the container along the same axis.
+    Percent(f64),
+    /// Decided by the flexbox. Auto margins absorb the free space on the axis of their side,
+    /// before it is used to justify or align the items. Free space is split evenly between all
+    /// the auto margins on an axis. Auto minimum sizes are 0, auto maximum sizes don't limit the
+    /// size, other auto lengths don't take any space.
+    Auto,
+}
+
+impl Length {
+    /// Return this length with a percentage resolved relative to `size`.
+    fn resolve(self, size: usize) -> Self {
+        match self {
+            Self::Percent(percent) => Self::Cells((size as f64 * percent / 100.0) as usize),
+            length => length,
+        }
+    }
+
+    /// Return the fixed amount of cells of this length. Percentages have to be resolved first,
+    /// auto lengths don't take any space by themselves.
+    fn cells(self) -> usize {
+        match self {
+            Self::Cells(cells) => cells,
+            Self::Percent(_) | Self::Auto => 0,
+        }
+    }
+}
+
+impl Default for Length {
+    fn default() -> Self {
+        Self::Cells(0)
+    }
+}
+
+impl From<usize> for Length {
+    fn from(value: usize) -> Self {
+        Self::Cells(value)
+    }
+}
+
+impl From<u32> for Length {
+    fn from(value: u32) -> Self {
+        Self::Cells(value as usize)
+    }
+}
+
+/// Integer literals without a type are `i32`, so this makes `Length` accept them. Negative
+/// amounts of cells are treated as 0.
+impl From<i32> for Length {
+    fn from(value: i32) -> Self {
+        Self::Cells(value.max(0) as usize)
+    }
+}
+
+impl Display for Length {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cells(cells) => write!(f, "{cells}"),
+            Self::Percent(percent) => write!(f, "{percent}%"),
+            Self::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+// https://developer.mozilla.org/en-US/docs/Web/CSS/position
+// https://w3c.github.io/csswg-drafts/css-flexbox/#abspos-items
+/// How an item is positioned in a flexbox.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Position {
+    /// The item is layed out together with the other items in the flexbox.
+    #[default]
+    Static,
+    /// The item is taken out of the flow of the flexbox and positioned relative to its edges with
+    /// its inset. It doesn't take up any space, so it can overlap other items.
+    Absolute,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Static => "static",
+                Self::Absolute => "absolute",
+            }
+        )
+    }
+}
+
+// https://developer.mozilla.org/en-US/docs/Web/CSS/visibility
+// https://w3c.github.io/csswg-drafts/css-flexbox/#visibility-collapse
+/// Whether an item in a flexbox is shown.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Visibility {
+    /// The item is shown.
+    #[default]
+    Visible,
+    /// The item is not drawn and can't receive focus or mouse events, but still takes up its space
+    /// in the flexbox.
+    Hidden,
+    /// The item is left out of the layout entirely, together with the gap next to it.
+    Collapsed,
+}
+
+impl Display for Visibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Visible => "visible",
+                Self::Hidden => "hidden",
+                Self::Collapsed => "collapse",
+            }
+        )
+    }
+}
+
+/// How a view is placed along an axis inside of the space that is assigned to its item.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Placement {
+    /// Stretch the view to fill all the space of its item.
+    #[default]
+    Stretch,
+    /// Give the view its required size and place it at the left or top side of its item.
+    Start,
+    /// Give the view its required size and place it in the center of its item.
+    Center,
+    /// Give the view its required size and place it at the right or bottom side of its item.
+    End,
+}
+
+impl Display for Placement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Stretch => "stretch",
+                Self::Start => "start",
+                Self::Center => "center",
+                Self::End => "end",
+            }
+        )
+    }
+}
+
+/// A value for each side of a rectangle.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sides<T> {
+    /// The value for the top side.
+    pub top: T,
+    /// The value for the right side.
+    pub right: T,
+    /// The value for the bottom side.
+    pub bottom: T,
+    /// The value for the left side.
+    pub left: T,
+}
+
+impl<T: Copy> Sides<T> {
+    /// Create sides with a value for each side, in clockwise order starting from the top.
+    pub fn new(top: T, right: T, bottom: T, left: T) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Create sides with the same value for all sides.
+    pub fn all(value: T) -> Self {
+        Self::new(value, value, value, value)
+    }
+
+    /// Create sides with a value for the top and bottom side, and a value for the left and right
+    /// side.
+    pub fn symmetric(vertical: T, horizontal: T) -> Self {
+        Self::new(vertical, horizontal, vertical, horizontal)
+    }
+}
+
+impl<T> Sides<T> {
+    /// Create sides by applying `f` to the value of each side.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Sides<U> {
+        Sides {
+            top: f(self.top),
+            right: f(self.right),
+            bottom: f(self.bottom),
+            left: f(self.left),
+        }
+    }
+}
+
+/// Where the cells go that are left over when space is divided over items, since cells can't be
+/// split.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingPolicy {
+    /// Left over cells go to the items that lost the largest part of a cell by rounding down. Ties
+    /// are broken in favor of the items closest to the end.
+    #[default]
+    LargestRemainder,
+    /// Left over cells go to the items closest to the start.
+    StartBiased,
+    /// Left over cells go to the items closest to the end.
+    EndBiased,
+    /// Left over cells go to the items closest to the center.
+    CenterBiased,
+}
+
+impl RoundingPolicy {
+    /// Divide `space` cells in `parts` parts of equal size. The sizes of the parts differ by at most
+    /// one cell.
+    fn distribute(self, space: usize, parts: usize) -> Vec<usize> {
+        self.round(&vec![space as f64 / parts as f64; parts])
+    }
+
+    /// Round `sizes` to whole cells, so that the rounded sizes add up to the rounded sum of
+    /// `sizes`. Sizes are rounded down, after which the left over cells are handed out to sizes
+    /// that were rounded down, one cell each.
+    fn round(self, sizes: &[f64]) -> Vec<usize> {
+        let mut rounded_sizes: Vec<usize> =
+            sizes.iter().map(|size| size.floor() as usize).collect();
+        let mut left_over_cells =
+            (sizes.iter().sum::<f64>().round() as usize).saturating_sub(rounded_sizes.iter().sum());
+        let remainder = |index: usize| sizes[index] - sizes[index].floor();
+        // Remainders this small are caused by floating point errors, not actual division.
+        let mut candidates: Vec<usize> = (0..sizes.len())
+            .filter(|&index| remainder(index) > 1e-9)
+            .collect();
+        match self {
+            Self::LargestRemainder => {
+                // Sorting is stable, so reversing first breaks ties in favor of the end.
+                candidates.reverse();
+                candidates.sort_by(|&a, &b| remainder(b).total_cmp(&remainder(a)));
+            },
+            Self::StartBiased => {},
+            Self::EndBiased => candidates.reverse(),
+            Self::CenterBiased => {
+                candidates.sort_by_key(|&index| (2 * index).abs_diff(sizes.len().saturating_sub(1)))
+            },
+        }
+        for index in candidates {
+            if left_over_cells == 0 {
+                break;
+            }
+            rounded_sizes[index] += 1;
+            left_over_cells -= 1;
+        }
+        rounded_sizes
+    }
+}
+
+impl Display for RoundingPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::LargestRemainder => "largest-remainder",
+                Self::StartBiased => "start-biased",
+                Self::EndBiased => "end-biased",
+                Self::CenterBiased => "center-biased",
+            }
+        )
+    }
+}
+
+// https://w3c.github.io/csswg-drafts/css-align/#synthesize-baseline
+/// Where the baseline of an item is when it isn't set explicitly with [Flexbox::set_baseline].
+/// Cursive views don't report a baseline, and the flexbox doesn't look at the type of a view, so
+/// the same fallback applies to every item without an explicit baseline.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BaselineFallback {
+    /// Synthesize the baseline from the bottom row of the item.
+    #[default]
+    LastRow,
+    /// Use the top row of the item as its baseline. This suits items that show text starting at
+    /// their first row, like labels, inputs and buttons.
+    FirstRow,
+}
+
+impl Display for BaselineFallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::LastRow => "last-row",
+                Self::FirstRow => "first-row",
+            }
+        )
+    }
+}
+
+/// How much space a flexbox requests from its parent.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SizingPolicy {
+    /// Take up all the space that is available.
+    #[default]
+    Fill,
+    /// Only take up the space that is needed to fit the content, including gaps and padding.
+    FitContent,
+}
+
+impl Display for SizingPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Fill => "fill",
+                Self::FitContent => "fit-content",
             }
         )
     }
@@ -270,90 +854,108 @@ enum FlexboxError {
 impl FlexboxLayout {
     /// Return all the child items along with their absolute position. This makes drawing the
     /// flexbox very simple.
-    pub fn windows(&mut self) -> Vec<(Rc<RefCell<FlexItem>>, Rect)> {
+    pub fn windows(&mut self) -> Vec<(Rc<RefCell<FlexItem>>, Window)> {
         let mut windows = Vec::new();
-        let mut cross_offset = 0;
-        let mut assignable_free_space = self.cross_axis_free_space();
+        let free_space = self.cross_axis_free_space();
+        let assignable_free_space = free_space.max(0) as usize;
 
-        for (axis_index, axis) in self.main_axes.iter().enumerate() {
-            match self.options.axes_alignment {
-                AlignContent::FlexEnd => {
-                    if assignable_free_space > 0 {
-                        cross_offset += assignable_free_space;
-                    }
-                    assignable_free_space = 0;
+        let mut axes_alignment = self.options.axes_alignment;
+        if free_space < 0 && self.options.align_overflow == OverflowAlignment::Safe {
+            axes_alignment = AlignContent::FlexStart;
+        }
+        // Main axes that overflow the cross axis are pushed past the cross-start side, depending
+        // on the alignment.
+        let mut cross_offset = if free_space < 0 {
+            match axes_alignment {
+                AlignContent::FlexEnd => free_space,
+                AlignContent::Center | AlignContent::SpaceAround | AlignContent::SpaceEvenly => {
+                    free_space / 2
                 },
+                _ => 0,
+            }
+        } else {
+            0
+        };
+
+        // The free space that is added before each axis, and to the size of each axis.
+        let number_of_axes = self.main_axes.len();
+        let rounding = self.options.rounding;
+        let mut space_before = vec![0; number_of_axes];
+        let mut extra_cross_axis_size = vec![0; number_of_axes];
+        if number_of_axes > 0 {
+            match axes_alignment {
+                AlignContent::FlexStart => {},
+                AlignContent::FlexEnd => space_before[0] = assignable_free_space,
                 AlignContent::Center => {
-                    if assignable_free_space > 0 {
-                        cross_offset += assignable_free_space / 2;
-                    }
-                    assignable_free_space = 0;
+                    space_before[0] = rounding.distribute(assignable_free_space, 2)[0]
                 },
-                AlignContent::SpaceAround => {
-                    let assigned_space =
-                        assignable_free_space / (self.main_axes.len() * 2 - axis_index * 2);
-                    if assignable_free_space > 0 {
-                        cross_offset += assigned_space;
-                    }
-                    assignable_free_space -= assigned_space;
+                AlignContent::Stretch => {
+                    // The free space is added to the size of the axes themselves, so their items
+                    // can stretch into it.
+                    extra_cross_axis_size =
+                        rounding.distribute(assignable_free_space, number_of_axes)
                 },
-                _ => {},
-            }
-            for mut combo in axis.windows(self) {
-                match self.options.direction {
-                    FlexDirection::Row => combo.1.offset(XY::from((0, cross_offset))),
-                    FlexDirection::Column => combo.1.offset(XY::from((cross_offset, 0))),
-                }
-                windows.push(combo);
-            }
-            match self.options.axes_alignment {
                 AlignContent::SpaceBetween => {
-                    if assignable_free_space > 0 {
-                        let assigned_space =
-                            assignable_free_space / (self.main_axes.len() - axis_index - 1);
-                        if assignable_free_space > 0 {
-                            cross_offset += assigned_space;
-                        }
-                        assignable_free_space -= assigned_space;
-                    }
-                },
-                AlignContent::Stretch => {
-                    let assigned_space =
-                        assignable_free_space / (self.main_axes.len() - axis_index);
-                    if assignable_free_space > 0 {
-                        cross_offset += assigned_space;
-                    }
-                    assignable_free_space -= assigned_space;
+                    // The last axis has no space after it, a single axis stays at the start.
+                    let gaps = rounding.distribute(assignable_free_space, number_of_axes - 1);
+                    space_before[1..].copy_from_slice(&gaps);
                 },
                 AlignContent::SpaceAround => {
-                    let assigned_space =
-                        assignable_free_space / (self.main_axes.len() * 2 - (axis_index * 2 + 1));
-                    if assignable_free_space > 0 {
-                        cross_offset += assigned_space;
+                    let halves = rounding.distribute(assignable_free_space, number_of_axes * 2);
+                    for (axis_index, space) in space_before.iter_mut().enumerate() {
+                        *space = halves[axis_index * 2]
+                            + if axis_index > 0 {
+                                halves[axis_index * 2 - 1]
+                            } else {
+                                0
+                            };
                     }
-                    assignable_free_space -= assigned_space;
                 },
-                _ => {},
+                AlignContent::SpaceEvenly => {
+                    let gaps = rounding.distribute(assignable_free_space, number_of_axes + 1);
+                    space_before.copy_from_slice(&gaps[..number_of_axes]);
+                },
+            }
+        }
+
+        for (axis_index, axis) in self.main_axes.iter().enumerate() {
+            let cross_axis_size = axis.cross_axis_size(self) + extra_cross_axis_size[axis_index];
+            cross_offset += space_before[axis_index] as isize;
+            for (item, window) in axis.windows(self, cross_axis_size) {
+                let window = match self.options.direction {
+                    FlexDirection::Row | FlexDirection::RowReverse => {
+                        window.offset((0, cross_offset))
+                    },
+                    FlexDirection::Column | FlexDirection::ColumnReverse => {
+                        window.offset((cross_offset, 0))
+                    },
+                };
+                windows.push((item, window));
             }
-            cross_offset += axis.cross_axis_size(self) + self.options.cross_axis_gap as usize;
+            cross_offset += (cross_axis_size + self.cross_axis_gap()) as isize;
         }
 
         windows
     }
 
-    /// Return the amount of left over space on the cross axis.
-    pub fn cross_axis_free_space(&self) -> usize {
+    /// Return the amount of left over space on the cross axis. This is negative if the main axes
+    /// overflow the container.
+    pub fn cross_axis_free_space(&self) -> isize {
         let mut used_space = 0;
 
         for axis in &self.main_axes {
             used_space += axis.cross_axis_size(self);
         }
 
-        used_space += (self.main_axis_count() - 1) * self.options.cross_axis_gap as usize;
+        used_space += self.main_axis_count().saturating_sub(1) * self.cross_axis_gap();
 
         match self.options.direction {
-            FlexDirection::Row => self.size.y.saturating_sub(used_space),
-            FlexDirection::Column => self.size.x.saturating_sub(used_space),
+            FlexDirection::Row | FlexDirection::RowReverse => {
+                self.size.y as isize - used_space as isize
+            },
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                self.size.x as isize - used_space as isize
+            },
         }
     }
 
@@ -394,6 +996,10 @@ impl FlexboxLayout {
                 }
             }
 
+            // The items on the axis are known now, so their flexible lengths can be resolved.
+            main_axis.main_axis_sizes =
+                main_axis.resolve_main_axis_sizes(&RefCell::borrow(&layout));
+
             // PERF: Inserting elements at the front isn't ideal for performance.
             match options.wrap {
                 FlexWrap::NoWrap | FlexWrap::Wrap => {
@@ -408,83 +1014,537 @@ impl FlexboxLayout {
         layout
     }
 
-    /// Return the size of a [FlexItem] along the main axis.
+    /// Return the size of a [FlexItem] along the main axis, as decided by its flex-basis.
     pub fn flexitem_main_axis_size(&self, item: &mut FlexItem) -> usize {
-        match self.options.direction {
-            FlexDirection::Row => item.view.required_size(self.size).x,
-            FlexDirection::Column => item.view.required_size(self.size).y,
+        if let Some(main_axis_size) = self.flexitem_stretched_main_axis_size(item) {
+            return main_axis_size;
+        }
+        match item.flex_basis {
+            FlexBasis::Auto | FlexBasis::Content => match self.options.direction {
+                FlexDirection::Row | FlexDirection::RowReverse => {
+                    item.view.required_size(self.size).x
+                },
+                FlexDirection::Column | FlexDirection::ColumnReverse => {
+                    item.view.required_size(self.size).y
+                },
+            },
+            FlexBasis::Cells(cells) => cells,
+            FlexBasis::Percent(percent) => {
+                (self.container_main_axis_size() as f64 * percent / 100.0) as usize
+            },
+            FlexBasis::Zero => 0,
         }
     }
 
-    /// Return the amount of main axes in this layout.
-    pub fn main_axis_count(&self) -> usize {
-        self.main_axes.len()
+    /// Return the flex-basis of a [FlexItem] with an aspect ratio and an automatic flex-basis that
+    /// is derived from its stretched cross size, if that's the case. This flex-basis isn't allowed
+    /// to take more space than the other items in the line leave.
+    pub fn flexitem_stretched_main_axis_size(&self, item: &FlexItem) -> Option<usize> {
+        match item.flex_basis {
+            FlexBasis::Auto | FlexBasis::Content => self.flexitem_aspect_ratio_main_axis_size(
+                item,
+                self.flexitem_stretched_cross_axis_size(item)?,
+            ),
+            _ => None,
+        }
     }
-}
 
-/// A single main axis of a flexbox. In a flexbox without wrap, this will be the only main axis and
+    /// Return the size of a [FlexItem] along the main axis, as decided by its flex-basis and
+    /// clamped by its minimum and maximum size.
+    pub fn flexitem_hypothetical_main_axis_size(&self, item: &mut FlexItem) -> usize {
+        let (minimum_size, maximum_size) = self.flexitem_main_axis_limits(item);
+        clamp_size(
+            self.flexitem_main_axis_size(item),
+            minimum_size,
+            maximum_size,
+        )
+    }
+
+    /// Return the size of a [FlexItem] along the cross axis, clamped by its minimum and maximum
+    /// size. The item is measured with its resolved main axis size, so content that wraps (like
+    /// text in a row) gets the cross axis size it needs at that size.
+    pub fn flexitem_cross_axis_size(&self, item: &mut FlexItem, main_axis_size: usize) -> usize {
+        let (minimum_size, maximum_size) = self.flexitem_cross_axis_limits(item);
+        if let Some(cross_axis_size) =
+            self.flexitem_aspect_ratio_cross_axis_size(item, main_axis_size)
+        {
+            return clamp_size(cross_axis_size, minimum_size, maximum_size);
+        }
+        let cross_axis_size = match self.options.direction {
+            FlexDirection::Row | FlexDirection::RowReverse => {
+                item.view
+                    .required_size(XY::from((main_axis_size, self.size.y)))
+                    .y
+            },
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                item.view
+                    .required_size(XY::from((self.size.x, main_axis_size)))
+                    .x
+            },
+        };
+        clamp_size(cross_axis_size, minimum_size, maximum_size)
+    }
+
+    /// Return the size of a [FlexItem] along the cross axis if it is stretched to a definite size.
+    /// This is only the case in a single-line flexbox, where the size of the line is the cross
+    /// size of the container.
+    /// <https://w3c.github.io/csswg-drafts/css-flexbox/#definite-sizes>
+    pub fn flexitem_stretched_cross_axis_size(&self, item: &FlexItem) -> Option<usize> {
+        let (margin_start, margin_end) = self.flexitem_cross_axis_margins(item);
+        if self.options.wrap != FlexWrap::NoWrap
+            || item.align_self.resolve(self.options.item_alignment) != AlignItems::Stretch
+            || margin_start == Length::Auto
+            || margin_end == Length::Auto
+        {
+            return None;
+        }
+        let (minimum_size, maximum_size) = self.flexitem_cross_axis_limits(item);
+        Some(clamp_size(
+            self.container_cross_axis_size()
+                .saturating_sub(margin_start.cells() + margin_end.cells()),
+            minimum_size,
+            maximum_size,
+        ))
+    }
+
+    /// Return the width of a [FlexItem] in cells divided by its height in cells, as required by
+    /// its aspect ratio.
+    pub fn flexitem_cell_aspect_ratio(&self, item: &FlexItem) -> Option<f64> {
+        item.aspect_ratio
+            .filter(|&aspect_ratio| aspect_ratio > 0.0)
+            .map(|aspect_ratio| aspect_ratio * CELL_ASPECT_RATIO)
+    }
+
+    /// Return the size of a [FlexItem] along the main axis that keeps its aspect ratio at
+    /// `cross_axis_size`, if it has an aspect ratio.
+    pub fn flexitem_aspect_ratio_main_axis_size(
+        &self,
+        item: &FlexItem,
+        cross_axis_size: usize,
+    ) -> Option<usize> {
+        let cell_aspect_ratio = self.flexitem_cell_aspect_ratio(item)?;
+        let main_axis_size = match self.options.direction {
+            FlexDirection::Row | FlexDirection::RowReverse => {
+                cross_axis_size as f64 * cell_aspect_ratio
+            },
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                cross_axis_size as f64 / cell_aspect_ratio
+            },
+        };
+        Some(main_axis_size.round() as usize)
+    }
+
+    /// Return the size of a [FlexItem] along the cross axis that keeps its aspect ratio at
+    /// `main_axis_size`, if it has an aspect ratio.
+    pub fn flexitem_aspect_ratio_cross_axis_size(
+        &self,
+        item: &FlexItem,
+        main_axis_size: usize,
+    ) -> Option<usize> {
+        let cell_aspect_ratio = self.flexitem_cell_aspect_ratio(item)?;
+        let cross_axis_size = match self.options.direction {
+            FlexDirection::Row | FlexDirection::RowReverse => {
+                main_axis_size as f64 / cell_aspect_ratio
+            },
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                main_axis_size as f64 * cell_aspect_ratio
+            },
+        };
+        Some(cross_axis_size.round() as usize)
+    }
+
+    /// Return whether a [FlexItem] is aligned on its baseline. Baselines only exist along the
+    /// vertical axis, so items in a column never are. Neither are items with an auto margin on the
+    /// cross axis.
+    pub fn flexitem_participates_in_baseline_alignment(&self, item: &FlexItem) -> bool {
+        let (margin_start, margin_end) = self.flexitem_cross_axis_margins(item);
+        item.align_self.resolve(self.options.item_alignment) == AlignItems::Baseline
+            && matches!(
+                self.options.direction,
+                FlexDirection::Row | FlexDirection::RowReverse
+            )
+            && margin_start != Length::Auto
+            && margin_end != Length::Auto
+    }
+
+    /// Return the baseline of a [FlexItem] with `cross_axis_size`, including its cross-start
+    /// margin. Items without an explicit baseline get one from the baseline fallback.
+    pub fn flexitem_outer_baseline(&self, item: &FlexItem, cross_axis_size: usize) -> usize {
+        let (margin_start, _) = self.flexitem_cross_axis_margins(item);
+        let baseline = item
+            .baseline
+            .unwrap_or(match self.options.baseline_fallback {
+                BaselineFallback::LastRow => cross_axis_size.saturating_sub(1),
+                BaselineFallback::FirstRow => 0,
+            });
+        margin_start.cells() + baseline
+    }
+
+    /// Return the size of a [FlexItem] along the main axis including its margins, as decided by its
+    /// flex-basis and clamped by its minimum and maximum size.
+    pub fn flexitem_outer_hypothetical_main_axis_size(&self, item: &mut FlexItem) -> usize {
+        let (margin_start, margin_end) = self.flexitem_main_axis_margins(item);
+        margin_start.cells() + self.flexitem_hypothetical_main_axis_size(item) + margin_end.cells()
+    }
+
+    /// Return the max-content contribution of a [FlexItem] along the main axis, the larger of its
+    /// flex-basis and the size its view requires, clamped by its minimum and maximum size and
+    /// including its margins.
+    pub fn flexitem_outer_max_content_main_axis_size(&self, item: &mut FlexItem) -> usize {
+        let (margin_start, margin_end) = self.flexitem_main_axis_margins(item);
+        let (minimum_size, maximum_size) = self.flexitem_main_axis_limits(item);
+        let required_size = match self.options.direction {
+            FlexDirection::Row | FlexDirection::RowReverse => item.view.required_size(self.size).x,
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                item.view.required_size(self.size).y
+            },
+        };
+        let main_axis_size = clamp_size(
+            self.flexitem_main_axis_size(item).max(required_size),
+            minimum_size,
+            maximum_size,
+        );
+        margin_start.cells() + main_axis_size + margin_end.cells()
+    }
+
+    /// Return the margins of a [FlexItem] at the main-start and main-end side, before the layout
+    /// is mirrored for reverse directions. Percentages are resolved relative to the container.
+    pub fn flexitem_main_axis_margins(&self, item: &FlexItem) -> (Length, Length) {
+        let (margin_start, margin_end) = match self.options.direction {
+            FlexDirection::Row => (item.margin.left, item.margin.right),
+            FlexDirection::RowReverse => (item.margin.right, item.margin.left),
+            FlexDirection::Column => (item.margin.top, item.margin.bottom),
+            FlexDirection::ColumnReverse => (item.margin.bottom, item.margin.top),
+        };
+        let container_size = self.container_main_axis_size();
+        (
+            margin_start.resolve(container_size),
+            margin_end.resolve(container_size),
+        )
+    }
+
+    /// Return the margins of a [FlexItem] at the cross-start and cross-end side. Percentages are
+    /// resolved relative to the container.
+    pub fn flexitem_cross_axis_margins(&self, item: &FlexItem) -> (Length, Length) {
+        let (margin_start, margin_end) = match self.options.direction {
+            FlexDirection::Row | FlexDirection::RowReverse => (item.margin.top, item.margin.bottom),
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                (item.margin.left, item.margin.right)
+            },
+        };
+        let container_size = self.container_cross_axis_size();
+        (
+            margin_start.resolve(container_size),
+            margin_end.resolve(container_size),
+        )
+    }
+
+    /// Return the minimum and maximum size of a [FlexItem] along the main axis.
+    pub fn flexitem_main_axis_limits(&self, item: &FlexItem) -> (usize, Option<usize>) {
+        let (minimum_size, maximum_size) = match self.options.direction {
+            FlexDirection::Row | FlexDirection::RowReverse => (item.min_size.x, item.max_size.x),
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                (item.min_size.y, item.max_size.y)
+            },
+        };
+        resolve_limits(minimum_size, maximum_size, self.container_main_axis_size())
+    }
+
+    /// Return the minimum and maximum size of a [FlexItem] along the cross axis.
+    pub fn flexitem_cross_axis_limits(&self, item: &FlexItem) -> (usize, Option<usize>) {
+        let (minimum_size, maximum_size) = match self.options.direction {
+            FlexDirection::Row | FlexDirection::RowReverse => (item.min_size.y, item.max_size.y),
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                (item.min_size.x, item.max_size.x)
+            },
+        };
+        resolve_limits(minimum_size, maximum_size, self.container_cross_axis_size())
+    }
+
+    /// Return the size of the container along the main axis.
+    pub fn container_main_axis_size(&self) -> usize {
+        match self.options.direction {
+            FlexDirection::Row | FlexDirection::RowReverse => self.size.x,
+            FlexDirection::Column | FlexDirection::ColumnReverse => self.size.y,
+        }
+    }
+
+    /// Return the size of the container along the cross axis.
+    pub fn container_cross_axis_size(&self) -> usize {
+        match self.options.direction {
+            FlexDirection::Row | FlexDirection::RowReverse => self.size.y,
+            FlexDirection::Column | FlexDirection::ColumnReverse => self.size.x,
+        }
+    }
+
+    /// Return the gap between items on the main axis, with a percentage relative to the size of
+    /// the container along the main axis.
+    pub fn main_axis_gap(&self) -> usize {
+        self.options
+            .main_axis_gap
+            .resolve(self.container_main_axis_size())
+            .cells()
+    }
+
+    /// Return the gap between the main axes, with a percentage relative to the size of the
+    /// container along the cross axis.
+    pub fn cross_axis_gap(&self) -> usize {
+        self.options
+            .cross_axis_gap
+            .resolve(self.container_cross_axis_size())
+            .cells()
+    }
+
+    /// Return the index of the main axis that `item` is in, counting from the top or left side.
+    pub fn main_axis_index(&self, item: &Rc<RefCell<FlexItem>>) -> Option<usize> {
+        self.main_axes.iter().position(|axis| {
+            axis.items
+                .iter()
+                .any(|axis_item| Weak::as_ptr(axis_item) == Rc::as_ptr(item))
+        })
+    }
+
+    /// Return the amount of main axes in this layout.
+    pub fn main_axis_count(&self) -> usize {
+        self.main_axes.len()
+    }
+
+    /// Return the size that is needed to fit all the items, without any free space. This can be
+    /// larger than the container if the items overflow it.
+    pub fn content_size(&self) -> XY<usize> {
+        let main_axis_size = self
+            .main_axes
+            .iter()
+            .map(|axis| axis.content_main_axis_size(self))
+            .max()
+            .unwrap_or(0);
+        let cross_axis_size = self
+            .main_axes
+            .iter()
+            .map(|axis| axis.content_cross_axis_size(self))
+            .sum::<usize>()
+            + self.main_axis_count().saturating_sub(1) * self.cross_axis_gap();
+        match self.options.direction {
+            FlexDirection::Row | FlexDirection::RowReverse => {
+                XY::from((main_axis_size, cross_axis_size))
+            },
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                XY::from((cross_axis_size, main_axis_size))
+            },
+        }
+    }
+}
+
+/// A single main axis of a flexbox. In a flexbox without wrap, this will be the only main axis and
 /// contain all the items. In a flexbox with wrap, this axis will only hold as many items as it can
 /// accomodate given the size of the main axis and the gapsize of the main axis.
 struct MainAxis {
     /// The items in this main axis.
     items: Vec<Weak<RefCell<FlexItem>>>,
-    /// Cache value for the remaining free space in this axis.
-    free_space: usize,
+    /// Cache value for the remaining free space in this axis. This is negative if the items
+    /// overflow the axis.
+    free_space: isize,
+    /// The resolved sizes of the items along the main axis, in the same order as `items`. These
+    /// are only known once all the items are added.
+    main_axis_sizes: Vec<usize>,
 }
 
 impl MainAxis {
     /// Create a new main axis part for the given layout.
     pub fn new(layout: Weak<RefCell<FlexboxLayout>>) -> Self {
         let layout_upgraded = layout.upgrade().unwrap();
-        let free_space = match RefCell::borrow(&layout_upgraded).options.direction {
-            FlexDirection::Row => RefCell::borrow(&layout_upgraded).size.x,
-            FlexDirection::Column => RefCell::borrow(&layout_upgraded).size.y,
-        };
+        let free_space = RefCell::borrow(&layout_upgraded).container_main_axis_size() as isize;
         MainAxis {
             items: Vec::new(),
             free_space,
+            main_axis_sizes: Vec::new(),
         }
     }
 
-    /// Return the cross axis size. The size of the cross axis is the maximum size of its elements
-    /// along the cross axis.
+    /// Return the cross axis size. In a single-line flexbox this is the cross size of the
+    /// container, otherwise it's the size of the content along the cross axis.
+    /// <https://w3c.github.io/csswg-drafts/css-flexbox/#algo-cross-line>
     pub fn cross_axis_size(&self, layout: &FlexboxLayout) -> usize {
-        let mut maximum_item_cross_axis_size = 0;
-        match layout.options.direction {
-            FlexDirection::Row => {
-                for item in &self.items {
-                    maximum_item_cross_axis_size = maximum_item_cross_axis_size.max(
-                        RefCell::borrow_mut(&item.upgrade().unwrap())
-                            .view
-                            .required_size(layout.size)
-                            .y,
-                    );
-                }
-            },
-            FlexDirection::Column => {
-                for item in &self.items {
-                    maximum_item_cross_axis_size = maximum_item_cross_axis_size.max(
-                        RefCell::borrow_mut(&item.upgrade().unwrap())
-                            .view
-                            .required_size(layout.size)
-                            .x,
-                    );
-                }
+        match layout.options.wrap {
+            FlexWrap::NoWrap => match layout.options.direction {
+                FlexDirection::Row | FlexDirection::RowReverse => layout.size.y,
+                FlexDirection::Column | FlexDirection::ColumnReverse => layout.size.x,
             },
+            FlexWrap::Wrap | FlexWrap::WrapReverse => self.content_cross_axis_size(layout),
         }
+    }
 
-        maximum_item_cross_axis_size
+    /// Return the size of the content along the main axis. This is the sum of the max-content
+    /// contributions of its elements, so each of them fits whatever its flex-basis is.
+    /// <https://w3c.github.io/csswg-drafts/css-flexbox/#intrinsic-main-sizes>
+    pub fn content_main_axis_size(&self, layout: &FlexboxLayout) -> usize {
+        self.items
+            .iter()
+            .map(|item| {
+                layout.flexitem_outer_max_content_main_axis_size(&mut RefCell::borrow_mut(
+                    &item.upgrade().unwrap(),
+                ))
+            })
+            .sum::<usize>()
+            + self.number_of_items().saturating_sub(1) * layout.main_axis_gap()
+    }
+
+    /// Return the size of the content along the cross axis. This is the maximum size of its
+    /// elements along the cross axis.
+    pub fn content_cross_axis_size(&self, layout: &FlexboxLayout) -> usize {
+        let mut maximum_item_cross_axis_size = 0;
+        // Items that are aligned on their baseline need to fit both above and below the baseline
+        // of the axis.
+        let mut maximum_size_below_baseline = 0;
+        for (item, &main_axis_size) in self.items.iter().zip(&self.main_axis_sizes) {
+            let item = item.upgrade().unwrap();
+            let (margin_start, margin_end) =
+                layout.flexitem_cross_axis_margins(&RefCell::borrow(&item));
+            let item_cross_axis_size =
+                layout.flexitem_cross_axis_size(&mut RefCell::borrow_mut(&item), main_axis_size);
+            let item_outer_cross_axis_size =
+                margin_start.cells() + item_cross_axis_size + margin_end.cells();
+            maximum_item_cross_axis_size =
+                maximum_item_cross_axis_size.max(item_outer_cross_axis_size);
+            if layout.flexitem_participates_in_baseline_alignment(&RefCell::borrow(&item)) {
+                let outer_baseline =
+                    layout.flexitem_outer_baseline(&RefCell::borrow(&item), item_cross_axis_size);
+                maximum_size_below_baseline = maximum_size_below_baseline
+                    .max(item_outer_cross_axis_size.saturating_sub(outer_baseline));
+            }
+        }
+
+        maximum_item_cross_axis_size.max(self.baseline(layout) + maximum_size_below_baseline)
+    }
+
+    /// Return the baseline of this axis, the largest baseline of the items that are aligned on
+    /// their baseline.
+    pub fn baseline(&self, layout: &FlexboxLayout) -> usize {
+        let mut maximum_baseline = 0;
+        for (item, &main_axis_size) in self.items.iter().zip(&self.main_axis_sizes) {
+            let item = item.upgrade().unwrap();
+            if layout.flexitem_participates_in_baseline_alignment(&RefCell::borrow(&item)) {
+                let item_cross_axis_size = layout
+                    .flexitem_cross_axis_size(&mut RefCell::borrow_mut(&item), main_axis_size);
+                maximum_baseline = maximum_baseline.max(
+                    layout.flexitem_outer_baseline(&RefCell::borrow(&item), item_cross_axis_size),
+                );
+            }
+        }
+
+        maximum_baseline
     }
 
     /// Returns the flexitems and their corresponding windows in the local coordinates (relative to
-    /// the topleft of the bounding box of this axis.
-    pub fn windows(&self, layout: &FlexboxLayout) -> Vec<(Rc<RefCell<FlexItem>>, Rect)> {
+    /// the topleft of the bounding box of this axis. `cross_axis_size` is the size of this axis
+    /// along the cross axis, which can be larger than its content when the axes are stretched.
+    pub fn windows(
+        &self,
+        layout: &FlexboxLayout,
+        cross_axis_size: usize,
+    ) -> Vec<(Rc<RefCell<FlexItem>>, Window)> {
         let mut windows = Vec::new();
         let mut offset = 0;
-        let mut assignable_free_space = self.free_space;
-        let combined_grow_factor = self.combined_grow_factor();
-        let mut remaining_grow_factor = combined_grow_factor;
-        let cross_axis_size = self.cross_axis_size(layout);
+        let main_axis_sizes = &self.main_axis_sizes;
+        let main_axis_margins: Vec<(Length, Length)> = self
+            .items
+            .iter()
+            .map(|item| {
+                layout.flexitem_main_axis_margins(&RefCell::borrow(&item.upgrade().unwrap()))
+            })
+            .collect();
+        // Free space is only left for justify-content if no item requested it with flex-grow.
+        let used_space = main_axis_sizes.iter().sum::<usize>()
+            + main_axis_margins
+                .iter()
+                .map(|(margin_start, margin_end)| margin_start.cells() + margin_end.cells())
+                .sum::<usize>()
+            + self.number_of_items().saturating_sub(1) * layout.main_axis_gap();
+        let free_space = layout.container_main_axis_size() as isize - used_space as isize;
+        let mut assignable_free_space = free_space.max(0) as usize;
+        let baseline = self.baseline(layout);
+        let rounding = layout.options.rounding;
+
+        let mut justification = layout.options.justification;
+        if free_space < 0 && layout.options.justify_overflow == OverflowAlignment::Safe {
+            justification = JustifyContent::Start;
+        }
+        let justification = justification.resolve(layout.options.direction);
+        // Items that overflow the axis are pushed past the main-start side, depending on the
+        // justification.
+        let main_axis_shift = if free_space < 0 {
+            match justification {
+                JustifyContent::FlexEnd => free_space,
+                JustifyContent::Center
+                | JustifyContent::SpaceAround
+                | JustifyContent::SpaceEvenly => free_space / 2,
+                _ => 0,
+            }
+        } else {
+            0
+        };
+
+        // Auto margins absorb the free space before justify-content gets to use it.
+        let auto_margins = main_axis_margins
+            .iter()
+            .flat_map(|(margin_start, margin_end)| [margin_start, margin_end])
+            .filter(|&&margin| margin == Length::Auto)
+            .count();
+        let mut auto_margin_sizes = rounding
+            .distribute(assignable_free_space, auto_margins)
+            .into_iter();
+        if auto_margins > 0 {
+            assignable_free_space = 0;
+        }
+        let resolved_main_axis_margins: Vec<(usize, usize)> = main_axis_margins
+            .iter()
+            .map(|&(margin_start, margin_end)| {
+                let mut resolve_margin = |margin: Length| match margin {
+                    Length::Auto => auto_margin_sizes.next().unwrap(),
+                    length => length.cells(),
+                };
+                (resolve_margin(margin_start), resolve_margin(margin_end))
+            })
+            .collect();
+
+        // The free space that is added before each item, as decided by the justification.
+        let number_of_items = self.number_of_items();
+        let mut space_before = vec![0; number_of_items];
+        if number_of_items > 0 {
+            match justification {
+                JustifyContent::FlexStart => {},
+                JustifyContent::FlexEnd => space_before[0] = assignable_free_space,
+                JustifyContent::Center => {
+                    space_before[0] = rounding.distribute(assignable_free_space, 2)[0]
+                },
+                JustifyContent::SpaceBetween => {
+                    // A single item stays at the start.
+                    let gaps = rounding.distribute(assignable_free_space, number_of_items - 1);
+                    space_before[1..].copy_from_slice(&gaps);
+                },
+                JustifyContent::SpaceAround => {
+                    let halves = rounding.distribute(assignable_free_space, number_of_items * 2);
+                    for (item_index, space) in space_before.iter_mut().enumerate() {
+                        *space = halves[item_index * 2]
+                            + if item_index > 0 {
+                                halves[item_index * 2 - 1]
+                            } else {
+                                0
+                            };
+                    }
+                },
+                JustifyContent::SpaceEvenly => {
+                    let gaps = rounding.distribute(assignable_free_space, number_of_items + 1);
+                    space_before.copy_from_slice(&gaps[..number_of_items]);
+                },
+                JustifyContent::Start
+                | JustifyContent::End
+                | JustifyContent::Left
+                | JustifyContent::Right => {
+                    unreachable!("justify-content is resolved relative to the main axis")
+                },
+            }
+        }
 
         for (item_index, item) in self
             .items
@@ -492,220 +1552,120 @@ impl MainAxis {
             .map(|item| item.upgrade().unwrap())
             .enumerate()
         {
-            let mut start_x = 0;
-            let mut start_y = 0;
-            let mut width = 1;
-            let mut height = 1;
-
-            if combined_grow_factor > 0 {
-                // Axis contains elements that want the free space. Give it to them, don't use
-                // justify-content.
-
-                let mut current_item_assigned_space = 0;
-                let item_main_axis_size =
-                    layout.flexitem_main_axis_size(&mut RefCell::borrow_mut(&item));
-                if remaining_grow_factor > 0 {
-                    current_item_assigned_space =
-                        ((RefCell::borrow(&item).flex_grow as f64 / remaining_grow_factor as f64)
-                            * assignable_free_space as f64) as usize;
-                }
-
-                match layout.options.direction {
-                    FlexDirection::Row => {
-                        start_x = offset;
-                        width = item_main_axis_size + current_item_assigned_space;
-                    },
-                    FlexDirection::Column => {
-                        start_y = offset;
-                        height = item_main_axis_size + current_item_assigned_space;
-                    },
-                }
-                offset += item_main_axis_size
-                    + layout.options.main_axis_gap as usize
-                    + current_item_assigned_space;
-                assignable_free_space -= current_item_assigned_space;
-                remaining_grow_factor -= RefCell::borrow(&item).flex_grow as usize;
-            } else {
-                // Axis doesn't contain elements that want free space. Use justify-content property
-                // to decide positioning.
-
-                match layout.options.direction {
-                    FlexDirection::Row => {
-                        width = RefCell::borrow_mut(&item).view.required_size(layout.size).x;
-                    },
-                    FlexDirection::Column => {
-                        height = RefCell::borrow_mut(&item).view.required_size(layout.size).y;
-                    },
-                }
-
-                // Decides `start_x`, `width` is item's preferred width.
-                match layout.options.justification {
-                    JustifyContent::FlexStart => {
-                        match layout.options.direction {
-                            FlexDirection::Row => {
-                                start_x = offset;
-                            },
-                            FlexDirection::Column => {
-                                start_y = offset;
-                            },
-                        }
-
-                        offset += layout.flexitem_main_axis_size(&mut RefCell::borrow_mut(&item))
-                            + layout.options.main_axis_gap as usize;
-                    },
-                    JustifyContent::FlexEnd => {
-                        if assignable_free_space > 0 {
-                            offset = assignable_free_space;
-                            assignable_free_space = 0;
-                        }
-                        match layout.options.direction {
-                            FlexDirection::Row => {
-                                start_x = offset;
-                            },
-                            FlexDirection::Column => {
-                                start_y = offset;
-                            },
-                        }
-
-                        offset += layout.flexitem_main_axis_size(&mut RefCell::borrow_mut(&item))
-                            + layout.options.main_axis_gap as usize;
-                    },
-                    JustifyContent::Center => {
-                        if assignable_free_space > 0 {
-                            offset = assignable_free_space / 2;
-                            assignable_free_space = 0;
-                        }
-
-                        match layout.options.direction {
-                            FlexDirection::Row => {
-                                start_x = offset;
-                            },
-                            FlexDirection::Column => {
-                                start_y = offset;
-                            },
-                        }
-
-                        offset += layout.flexitem_main_axis_size(&mut RefCell::borrow_mut(&item))
-                            + layout.options.main_axis_gap as usize;
-                    },
-                    JustifyContent::SpaceBetween => {
-                        match layout.options.direction {
-                            FlexDirection::Row => {
-                                start_x = offset;
-                            },
-                            FlexDirection::Column => {
-                                start_y = offset;
-                            },
-                        }
-
-                        if assignable_free_space > 0 && item_index + 1 < self.number_of_items() {
-                            let extra_free_space = assignable_free_space
-                                / (self.number_of_items().saturating_sub(1 + item_index));
-                            assignable_free_space -= extra_free_space;
-                            offset += extra_free_space;
-                        }
-                        offset += layout.flexitem_main_axis_size(&mut RefCell::borrow_mut(&item))
-                            + layout.options.main_axis_gap as usize;
-                    },
-                    JustifyContent::SpaceAround => {
-                        let mut extra_free_space =
-                            assignable_free_space / (self.number_of_items() * 2 - item_index * 2);
-                        if assignable_free_space > 0 {
-                            offset += extra_free_space;
-                        }
-                        assignable_free_space -= extra_free_space;
-
-                        match layout.options.direction {
-                            FlexDirection::Row => {
-                                start_x = offset;
-                            },
-                            FlexDirection::Column => {
-                                start_y = offset;
-                            },
-                        }
-
-                        extra_free_space = assignable_free_space
-                            / (self.number_of_items() * 2 - (item_index * 2 + 1));
-                        if assignable_free_space > 0 {
-                            offset += extra_free_space;
-                        }
-                        assignable_free_space -= extra_free_space;
-
-                        offset += layout.flexitem_main_axis_size(&mut RefCell::borrow_mut(&item))
-                            + layout.options.main_axis_gap as usize;
-                    },
-                    JustifyContent::SpaceEvenly => {
-                        let extra_free_space =
-                            assignable_free_space / (self.number_of_items() + 1 - item_index);
-                        if assignable_free_space > 0 {
-                            offset += extra_free_space;
-                        }
-                        assignable_free_space -= extra_free_space;
-
-                        match layout.options.direction {
-                            FlexDirection::Row => {
-                                start_x = offset;
-                            },
-                            FlexDirection::Column => {
-                                start_y = offset;
-                            },
-                        }
-
-                        offset += layout.flexitem_main_axis_size(&mut RefCell::borrow_mut(&item))
-                            + layout.options.main_axis_gap as usize;
-                    },
-                }
+            let item_main_axis_size = main_axis_sizes[item_index];
+            let (main_margin_start, main_margin_end) = resolved_main_axis_margins[item_index];
+            let item_outer_main_axis_size =
+                main_margin_start + item_main_axis_size + main_margin_end;
+
+            // Decides `main_start`, the size is the item's resolved main size.
+            offset += space_before[item_index];
+            let main_start = offset + main_margin_start;
+            offset += item_outer_main_axis_size + layout.main_axis_gap();
+
+            // Decides the position and size along the cross axis. Item's `layout()` called with
+            // this calculated size later.
+            let (cross_margin_start, cross_margin_end) =
+                layout.flexitem_cross_axis_margins(&RefCell::borrow(&item));
+            let cross_margins = cross_margin_start.cells() + cross_margin_end.cells();
+            let (minimum_size, maximum_size) =
+                layout.flexitem_cross_axis_limits(&RefCell::borrow(&item));
+            let item_cross_axis_size = layout
+                .flexitem_cross_axis_size(&mut RefCell::borrow_mut(&item), item_main_axis_size);
+            let mut item_alignment = RefCell::borrow(&item)
+                .align_self
+                .resolve(layout.options.item_alignment);
+            let cross_free_space =
+                cross_axis_size as isize - (item_cross_axis_size + cross_margins) as isize;
+            if (cross_free_space < 0 && layout.options.align_overflow == OverflowAlignment::Safe)
+                || (item_alignment == AlignItems::Baseline
+                    && !layout.flexitem_participates_in_baseline_alignment(&RefCell::borrow(&item)))
+                || (item_alignment == AlignItems::Stretch
+                    && layout
+                        .flexitem_cell_aspect_ratio(&RefCell::borrow(&item))
+                        .is_some())
+            {
+                item_alignment = AlignItems::FlexStart;
             }
+            let (cross_start, cross_size) =
+                if cross_margin_start == Length::Auto || cross_margin_end == Length::Auto {
+                    // Auto margins absorb the free space, which overrides the item alignment.
+                    let free_space = cross_free_space.max(0);
+                    let start_free_space = match (cross_margin_start, cross_margin_end) {
+                        (Length::Auto, Length::Auto) => {
+                            rounding.distribute(free_space as usize, 2)[0] as isize
+                        },
+                        (Length::Auto, _) => free_space,
+                        _ => 0,
+                    };
+                    (
+                        cross_margin_start.cells() as isize + start_free_space,
+                        item_cross_axis_size,
+                    )
+                } else {
+                    match item_alignment {
+                        AlignItems::FlexStart => {
+                            (cross_margin_start.cells() as isize, item_cross_axis_size)
+                        },
+                        AlignItems::FlexEnd => (
+                            cross_margin_start.cells() as isize + cross_free_space,
+                            item_cross_axis_size,
+                        ),
+                        AlignItems::Center => (
+                            cross_margin_start.cells() as isize
+                                + if cross_free_space > 0 {
+                                    rounding.distribute(cross_free_space as usize, 2)[0] as isize
+                                } else {
+                                    cross_free_space / 2
+                                },
+                            item_cross_axis_size,
+                        ),
+                        AlignItems::Stretch => (
+                            cross_margin_start.cells() as isize,
+                            clamp_size(
+                                cross_axis_size.saturating_sub(cross_margins),
+                                minimum_size,
+                                maximum_size,
+                            ),
+                        ),
+                        AlignItems::Baseline => (
+                            cross_margin_start.cells() as isize + baseline as isize
+                                - layout.flexitem_outer_baseline(
+                                    &RefCell::borrow(&item),
+                                    item_cross_axis_size,
+                                ) as isize,
+                            item_cross_axis_size,
+                        ),
+                    }
+                };
 
-            // Decides `start_y` and `height`. Item's `layout()` called with this calculated height
-            // later.
-            match layout.options.item_alignment {
-                AlignItems::FlexStart => match layout.options.direction {
-                    FlexDirection::Row => {
-                        start_y = 0;
-                        height = RefCell::borrow_mut(&item).view.required_size(layout.size).y;
-                    },
-                    FlexDirection::Column => {
-                        start_x = 0;
-                        width = RefCell::borrow_mut(&item).view.required_size(layout.size).x;
-                    },
-                },
-                AlignItems::FlexEnd => match layout.options.direction {
-                    FlexDirection::Row => {
-                        height = RefCell::borrow_mut(&item).view.required_size(layout.size).y;
-                        start_y = cross_axis_size - height;
-                    },
-                    FlexDirection::Column => {
-                        width = RefCell::borrow_mut(&item).view.required_size(layout.size).x;
-                        start_x = cross_axis_size - width;
-                    },
+            let main_start = main_start as isize + main_axis_shift;
+            let window = match layout.options.direction {
+                FlexDirection::Row | FlexDirection::RowReverse => {
+                    Window::from_size((main_start, cross_start), (item_main_axis_size, cross_size))
                 },
-                AlignItems::Center => match layout.options.direction {
-                    FlexDirection::Row => {
-                        height = RefCell::borrow_mut(&item).view.required_size(layout.size).y;
-                        start_y = (cross_axis_size - height) / 2;
-                    },
-                    FlexDirection::Column => {
-                        width = RefCell::borrow_mut(&item).view.required_size(layout.size).x;
-                        start_x = (cross_axis_size - width) / 2;
-                    },
+                FlexDirection::Column | FlexDirection::ColumnReverse => {
+                    Window::from_size((cross_start, main_start), (cross_size, item_main_axis_size))
                 },
-                AlignItems::Stretch => match layout.options.direction {
-                    FlexDirection::Row => {
-                        height = cross_axis_size;
-                        start_y = 0;
+            };
+            windows.push((item, window));
+        }
+
+        if layout.options.direction.is_reverse() {
+            // Mirror the items along the main axis, so main-start becomes the right or bottom side
+            // of the container. The windows are reversed as well to keep them in visual order.
+            for (_, window) in windows.iter_mut() {
+                match layout.options.direction {
+                    FlexDirection::Row | FlexDirection::RowReverse => {
+                        window.top_left.x =
+                            layout.size.x as isize - window.top_left.x - window.size.x as isize;
                     },
-                    FlexDirection::Column => {
-                        width = cross_axis_size;
-                        start_x = 0;
+                    FlexDirection::Column | FlexDirection::ColumnReverse => {
+                        window.top_left.y =
+                            layout.size.y as isize - window.top_left.y - window.size.y as isize;
                     },
-                },
+                }
             }
-
-            RefCell::borrow_mut(&item)
-                .view
-                .layout((width, height).into());
-            windows.push((item, Rect::from_size((start_x, start_y), (width, height))));
+            windows.reverse();
         }
 
         windows
@@ -719,15 +1679,13 @@ impl MainAxis {
     ) -> Result<(), FlexboxError> {
         let upgraded_item = item.upgrade().unwrap();
         if self.can_accomodate(&mut RefCell::borrow_mut(&upgraded_item), layout) {
-            self.free_space = self.free_space.saturating_sub(
-                layout.flexitem_main_axis_size(&mut RefCell::borrow_mut(&upgraded_item)),
-            );
+            self.free_space -= layout.flexitem_outer_hypothetical_main_axis_size(
+                &mut RefCell::borrow_mut(&upgraded_item),
+            ) as isize;
 
             // Only add gaps if there is already an item.
             if self.number_of_items() >= 1 {
-                self.free_space = self
-                    .free_space
-                    .saturating_sub(layout.options.main_axis_gap as usize);
+                self.free_space -= layout.main_axis_gap() as isize;
             }
 
             self.items.push(item);
@@ -739,22 +1697,22 @@ impl MainAxis {
     }
 
     /// Return whether this axis can accomodate `item` with the amount of free space it has left. A
-    /// main axis can accomodate an item if either it is the first axis in a non-wrapped flexbox,
-    /// or it has enough space for the item and possible gap that would be added.
+    /// main axis can accomodate an item if it is the only axis in a non-wrapped flexbox, or it has
+    /// enough space for the item and possible gap that would be added.
     pub fn can_accomodate(&self, item: &mut FlexItem, layout: &mut FlexboxLayout) -> bool {
         if let FlexWrap::NoWrap = layout.options.wrap {
-            // There can only be one main axis in a non-wrapping layout.
-            layout.main_axes.len() == 1
+            // There can only be one main axis in a non-wrapping layout, so it holds all items.
+            true
         } else if self.items.is_empty() {
             // Each main axis must be able to hold at least one item!
             true
         } else {
             let extra_used_space = if self.number_of_items() >= 1 {
-                layout.flexitem_main_axis_size(item) + layout.options.main_axis_gap as usize
+                layout.flexitem_outer_hypothetical_main_axis_size(item) + layout.main_axis_gap()
             } else {
-                layout.flexitem_main_axis_size(item)
+                layout.flexitem_outer_hypothetical_main_axis_size(item)
             };
-            extra_used_space <= self.free_space
+            extra_used_space as isize <= self.free_space
         }
     }
 
@@ -763,13 +1721,194 @@ impl MainAxis {
         self.items.len()
     }
 
-    /// Sum of the flex-grow of all the [FlexItem]s in this axis.
-    pub fn combined_grow_factor(&self) -> usize {
-        let mut total_grow_factor = 0usize;
-        self.items.iter().for_each(|item| {
-            total_grow_factor += RefCell::borrow(&item.upgrade().unwrap()).flex_grow as usize;
-        });
-        total_grow_factor
+    /// Return the sizes of the items along the main axis. These are the sizes the items request,
+    /// with the free space of this axis distributed over them according to their flex-grow, or the
+    /// overflowing space taken away from them according to their flex-shrink. Items that reach
+    /// their minimum or maximum size are frozen and the space they couldn't take is handed to the
+    /// other items.
+    /// <https://w3c.github.io/csswg-drafts/css-flexbox/#resolve-flexible-lengths>
+    pub fn resolve_main_axis_sizes(&self, layout: &FlexboxLayout) -> Vec<usize> {
+        let items: Vec<_> = self
+            .items
+            .iter()
+            .map(|item| item.upgrade().unwrap())
+            .collect();
+        let mut base_sizes: Vec<f64> = items
+            .iter()
+            .map(|item| layout.flexitem_main_axis_size(&mut RefCell::borrow_mut(item)) as f64)
+            .collect();
+        let limits: Vec<(usize, Option<usize>)> = items
+            .iter()
+            .map(|item| layout.flexitem_main_axis_limits(&RefCell::borrow(item)))
+            .collect();
+        let margins: usize = items
+            .iter()
+            .map(|item| {
+                let (margin_start, margin_end) =
+                    layout.flexitem_main_axis_margins(&RefCell::borrow(item));
+                margin_start.cells() + margin_end.cells()
+            })
+            .sum();
+        let available_space = layout.container_main_axis_size() as f64
+            - (margins + self.number_of_items().saturating_sub(1) * layout.main_axis_gap()) as f64;
+        let hypothetical_size = |index: usize, base_size: f64| -> f64 {
+            clamp_size(base_size as usize, limits[index].0, limits[index].1) as f64
+        };
+
+        // A flex-basis derived from a stretched cross size only takes the space the other items
+        // leave, otherwise an item with an aspect ratio would squeeze its siblings out of the line.
+        let stretched: Vec<bool> = items
+            .iter()
+            .map(|item| {
+                layout
+                    .flexitem_stretched_main_axis_size(&RefCell::borrow(item))
+                    .is_some()
+            })
+            .collect();
+        let stretched_space: f64 = (0..items.len())
+            .filter(|&index| stretched[index])
+            .map(|index| base_sizes[index])
+            .sum();
+        let leftover_space = (available_space
+            - (0..items.len())
+                .filter(|&index| !stretched[index])
+                .map(|index| hypothetical_size(index, base_sizes[index]))
+                .sum::<f64>())
+        .max(0.0);
+        if stretched_space > leftover_space {
+            for index in (0..items.len()).filter(|&index| stretched[index]) {
+                base_sizes[index] = (base_sizes[index] * leftover_space / stretched_space).floor();
+            }
+        }
+        let free_space = available_space
+            - (0..items.len())
+                .map(|index| hypothetical_size(index, base_sizes[index]))
+                .sum::<f64>();
+
+        let flex_factors: Vec<f64> = items
+            .iter()
+            .map(|item| {
+                // Negative flex factors are invalid, they're treated as 0.
+                if free_space > 0.0 {
+                    RefCell::borrow(item).flex_grow.max(0.0)
+                } else {
+                    RefCell::borrow(item).flex_shrink.max(0.0)
+                }
+            })
+            .collect();
+        // Large factors are scaled down, so adding them up can't overflow. Only their proportions
+        // matter, except where their sum is compared to 1, which is scaled back up.
+        let flex_scale = flex_factors.iter().copied().fold(1.0, f64::max);
+        let flex_factors: Vec<f64> = flex_factors
+            .into_iter()
+            .map(|flex_factor| flex_factor / flex_scale)
+            .collect();
+        let mut target_sizes = base_sizes.clone();
+        let mut frozen = vec![false; items.len()];
+
+        // Items that can't flex, or that would flex in the wrong direction because of their
+        // minimum or maximum size, keep their hypothetical size.
+        for index in 0..items.len() {
+            let hypothetical_size = hypothetical_size(index, base_sizes[index]);
+            if flex_factors[index] == 0.0
+                || (free_space > 0.0 && base_sizes[index] > hypothetical_size)
+                || (free_space <= 0.0 && base_sizes[index] < hypothetical_size)
+            {
+                frozen[index] = true;
+                target_sizes[index] = hypothetical_size;
+            }
+        }
+
+        let used_space = |frozen: &[bool], target_sizes: &[f64]| -> f64 {
+            (0..items.len())
+                .map(|index| {
+                    if frozen[index] {
+                        target_sizes[index]
+                    } else {
+                        base_sizes[index]
+                    }
+                })
+                .sum()
+        };
+        let initial_free_space = available_space - used_space(&frozen, &target_sizes);
+
+        loop {
+            let unfrozen: Vec<usize> = (0..items.len()).filter(|&index| !frozen[index]).collect();
+            if unfrozen.is_empty() {
+                break;
+            }
+            let mut remaining_free_space = available_space - used_space(&frozen, &target_sizes);
+            let combined_flex_factor: f64 = unfrozen.iter().map(|&index| flex_factors[index]).sum();
+            // Flex factors that add up to less than 1 only take that part of the free space, the
+            // rest stays free.
+            if combined_flex_factor * flex_scale < 1.0 {
+                let scaled_free_space = initial_free_space * combined_flex_factor * flex_scale;
+                if scaled_free_space.abs() < remaining_free_space.abs() {
+                    remaining_free_space = scaled_free_space;
+                }
+            }
+
+            if free_space > 0.0 {
+                for &index in &unfrozen {
+                    target_sizes[index] = base_sizes[index]
+                        + remaining_free_space * flex_factors[index] / combined_flex_factor;
+                }
+            } else {
+                // Shrinking is proportional to the size of an item as well, so small items don't
+                // disappear before big items shrink noticeably.
+                let combined_scaled_flex_factor: f64 = unfrozen
+                    .iter()
+                    .map(|&index| flex_factors[index] * base_sizes[index])
+                    .sum();
+                for &index in &unfrozen {
+                    target_sizes[index] = if combined_scaled_flex_factor > 0.0 {
+                        base_sizes[index]
+                            - remaining_free_space.abs() * (flex_factors[index] * base_sizes[index])
+                                / combined_scaled_flex_factor
+                    } else {
+                        base_sizes[index]
+                    };
+                }
+            }
+
+            // Fix violations of the minimum and maximum sizes, freezing the items that violate
+            // them in the direction of the total violation.
+            let mut total_violation = 0.0;
+            let mut violations = vec![0.0; items.len()];
+            for &index in &unfrozen {
+                let (minimum_size, maximum_size) = limits[index];
+                let mut clamped_size = target_sizes[index].max(minimum_size as f64);
+                if let Some(maximum_size) = maximum_size {
+                    clamped_size = clamped_size
+                        .min(maximum_size as f64)
+                        .max(minimum_size as f64);
+                }
+                violations[index] = clamped_size - target_sizes[index];
+                total_violation += violations[index];
+                target_sizes[index] = clamped_size;
+            }
+            for &index in &unfrozen {
+                if total_violation == 0.0
+                    || (total_violation > 0.0 && violations[index] > 0.0)
+                    || (total_violation < 0.0 && violations[index] < 0.0)
+                {
+                    frozen[index] = true;
+                }
+            }
+            // Every pass has to freeze an item to finish. If the sizes couldn't be computed, the
+            // remaining items keep their hypothetical size.
+            if unfrozen.iter().all(|&index| !frozen[index]) {
+                for &index in &unfrozen {
+                    frozen[index] = true;
+                    if !target_sizes[index].is_finite() {
+                        target_sizes[index] = hypothetical_size(index, base_sizes[index]);
+                    }
+                }
+            }
+        }
+
+        // Round the sizes together, so the rounding errors don't add up along the axis.
+        layout.options.rounding.round(&target_sizes)
     }
 }
 
@@ -801,25 +1940,202 @@ impl Flexbox {
     /// Remove all items.
     pub fn clear(&mut self) {
         self.content.clear();
+        self.focused = None;
         self.needs_relayout = true;
     }
 
-    /// Insert a view at `index`.
+    /// Insert a view at `index`. The focus stays on the item that has it.
     ///
     /// # Panics
     /// Panics if `index > self.len()`.
     pub fn insert(&mut self, index: usize, item: impl Into<FlexItem>) {
         self.content
             .insert(index, Rc::new(RefCell::new(item.into())));
+        if let Some(focused) = self.focused.as_mut() {
+            if *focused >= index {
+                *focused += 1;
+            }
+        }
+        self.needs_relayout = true;
+    }
+
+    /// Set the grow factor of an item. Factors can be fractional, negative factors are treated
+    /// as 0 and infinite or NaN factors are set to 0.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_flex_grow(&mut self, index: usize, flex_grow: impl Into<f64>) {
+        Rc::as_ref(&self.content[index]).borrow_mut().flex_grow =
+            finite_flex_factor(flex_grow.into());
+        self.needs_relayout = true;
+    }
+
+    /// Set the shrink factor of an item. Factors can be fractional, negative factors are treated
+    /// as 0 and infinite or NaN factors are set to 0.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_flex_shrink(&mut self, index: usize, flex_shrink: impl Into<f64>) {
+        Rc::as_ref(&self.content[index]).borrow_mut().flex_shrink =
+            finite_flex_factor(flex_shrink.into());
+        self.needs_relayout = true;
+    }
+
+    /// Set the initial main size of an item.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_flex_basis(&mut self, index: usize, flex_basis: FlexBasis) {
+        Rc::as_ref(&self.content[index]).borrow_mut().flex_basis = flex_basis;
+        self.needs_relayout = true;
+    }
+
+    /// Set the minimum size of an item along each axis. `None` means there is no minimum.
+    /// Percentages are relative to the size of the flexbox inside of its padding.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_min_size<T: Into<Length>>(&mut self, index: usize, min_size: XY<Option<T>>) {
+        Rc::as_ref(&self.content[index])
+            .borrow_mut()
+            .set_min_size(min_size);
+        self.needs_relayout = true;
+    }
+
+    /// Set the maximum size of an item along each axis. `None` means there is no maximum.
+    /// Percentages are relative to the size of the flexbox inside of its padding.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_max_size<T: Into<Length>>(&mut self, index: usize, max_size: XY<Option<T>>) {
+        Rc::as_ref(&self.content[index])
+            .borrow_mut()
+            .set_max_size(max_size);
+        self.needs_relayout = true;
+    }
+
+    /// Set the alignment of an item on the cross axis, overriding the align-items option.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_align_self(&mut self, index: usize, align_self: AlignSelf) {
+        Rc::as_ref(&self.content[index]).borrow_mut().align_self = align_self;
+        self.needs_relayout = true;
+    }
+
+    /// Set the order of an item. Items are layed out in ascending order, items with the same
+    /// order are layed out in the order they were added. This doesn't change the index of the
+    /// item.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_order(&mut self, index: usize, order: i32) {
+        Rc::as_ref(&self.content[index]).borrow_mut().order = order;
+        self.needs_relayout = true;
+    }
+
+    /// Set the margin of an item. Percentages are relative to the size of the flexbox inside of its
+    /// padding, along the axis of the side.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_margin<T: Into<Length>>(&mut self, index: usize, margin: Sides<T>) {
+        Rc::as_ref(&self.content[index])
+            .borrow_mut()
+            .set_margin(margin);
+        self.needs_relayout = true;
+    }
+
+    /// Set the baseline of an item, the offset of its first line of text from its cross-start
+    /// side. Set to `None` to use the baseline fallback of the flexbox. Views are never inspected
+    /// for their baseline, so views that are wrapped in another view (like a named view or a
+    /// panel) need their baseline set here if it differs from the fallback.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_baseline(&mut self, index: usize, baseline: Option<usize>) {
+        Rc::as_ref(&self.content[index]).borrow_mut().baseline = baseline;
+        self.needs_relayout = true;
+    }
+
+    /// Set the aspect ratio of an item, its width divided by its height as it appears on the
+    /// screen. Terminal cells are about twice as high as they are wide, so an item with an aspect
+    /// ratio of 1 is twice as many cells wide as it is high. The size of the item along the cross
+    /// axis is derived from its size along the main axis, so the item is never stretched. If it
+    /// would be stretched in a single-line flexbox, its flex-basis is derived from the cross size
+    /// of the flexbox instead, limited to the space the other items in the line leave. Set to
+    /// `None` to size both axes independently.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_aspect_ratio(&mut self, index: usize, aspect_ratio: Option<f64>) {
+        Rc::as_ref(&self.content[index]).borrow_mut().aspect_ratio = aspect_ratio;
+        self.needs_relayout = true;
+    }
+
+    /// Set whether an item is layed out with the other items, or taken out of the flow and
+    /// positioned relative to the edges of the flexbox with its inset.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_position(&mut self, index: usize, position: Position) {
+        Rc::as_ref(&self.content[index]).borrow_mut().position = position;
+        self.needs_relayout = true;
+    }
+
+    /// Set the offsets of an absolutely positioned item from the edges of the flexbox. Auto sides
+    /// are not fixed, an item with both sides of an axis fixed is sized to fit between them.
+    /// Without fixed sides, the item is placed at the start of the area inside of the padding.
+    /// Percentages are relative to the size of the flexbox.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_inset<T: Into<Length>>(&mut self, index: usize, inset: Sides<T>) {
+        Rc::as_ref(&self.content[index])
+            .borrow_mut()
+            .set_inset(inset);
+        self.needs_relayout = true;
+    }
+
+    /// Set the stacking order of an item. Items with a higher z-index are drawn on top of, and
+    /// receive mouse events before, items with a lower z-index. Items with the same z-index are
+    /// stacked in their visual order, with absolutely positioned items on top.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_z_index(&mut self, index: usize, z_index: i32) {
+        Rc::as_ref(&self.content[index]).borrow_mut().z_index = z_index;
+        self.needs_relayout = true;
+    }
+
+    /// Set how the view of an item is placed inside of the space that is assigned to the item,
+    /// along each axis. By default, views are stretched to fill the space of their item. With any
+    /// other placement, the view gets its required size instead. The item keeps all of its space,
+    /// so mouse events anywhere in that space still go to its view.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn set_placement(&mut self, index: usize, placement: XY<Placement>) {
+        Rc::as_ref(&self.content[index]).borrow_mut().placement = placement;
         self.needs_relayout = true;
     }
 
-    /// Set the grow factor of an item.
+    /// Set whether an item is shown. A hidden item keeps its space, a collapsed item is left out
+    /// of the layout. Neither is drawn or can keep the focus, but both keep their view and its
+    /// state. If the item has the focus, it moves to the next item in visual order that can take
+    /// it, or the previous one if there is no such item.
     ///
     /// # Panics
     /// Panics if `index >= self.len()`.
-    pub fn set_flex_grow(&mut self, index: usize, flex_grow: u8) {
-        Rc::as_ref(&self.content[index]).borrow_mut().flex_grow = flex_grow;
+    pub fn set_visible(&mut self, index: usize, visibility: Visibility) {
+        if visibility != Visibility::Visible && self.focused == Some(index) {
+            RefCell::borrow_mut(&self.content[index])
+                .view
+                .on_event(Event::FocusLost);
+            // The neighbours are found from the position of the item, while it's still visible.
+            self.focused = self.focus_neighbour(index);
+        }
+        Rc::as_ref(&self.content[index]).borrow_mut().visibility = visibility;
         self.needs_relayout = true;
     }
 
@@ -833,33 +2149,73 @@ impl Flexbox {
         self.content.is_empty()
     }
 
-    /// Remove an item from the flexbox.
+    /// Remove an item from the flexbox. The focus stays on the item that has it. If the removed
+    /// item has the focus, it moves to the next item in visual order that can take it, or the
+    /// previous one if there is no such item.
     ///
     /// # Panics
     /// Panics if `index >= self.len()`.
     pub fn remove(&mut self, index: usize) {
+        self.focused = match self.focused {
+            Some(focused) if focused > index => Some(focused - 1),
+            Some(focused) if focused == index => self
+                .focus_neighbour(index)
+                // Indices after the removed item shift down.
+                .map(|other| if other > index { other - 1 } else { other }),
+            focused => focused,
+        };
         self.content.remove(index);
+        self.needs_relayout = true;
     }
 
     /// Gap between items on the main axis.
-    pub fn main_axis_gap(&self) -> u32 {
+    pub fn main_axis_gap(&self) -> Length {
         self.options.main_axis_gap
     }
 
-    /// Set the fixed gap between elements on the main axis.
-    pub fn set_main_axis_gap(&mut self, gap: u32) {
-        self.options.main_axis_gap = gap;
+    /// Set the gap between elements on the main axis. A percentage is relative to the size of the
+    /// main axis, auto is the same as no gap.
+    pub fn set_main_axis_gap(&mut self, gap: impl Into<Length>) {
+        self.options.main_axis_gap = gap.into();
         self.needs_relayout = true;
     }
 
     /// Gap between the main axes.
-    pub fn cross_axis_gap(&self) -> u32 {
+    pub fn cross_axis_gap(&self) -> Length {
         self.options.cross_axis_gap
     }
 
-    /// Set the fixed gap between the main axes.
-    pub fn set_cross_axis_gap(&mut self, gap: u32) {
-        self.options.cross_axis_gap = gap;
+    /// Set the gap between the main axes. A percentage is relative to the size of the cross axis,
+    /// auto is the same as no gap.
+    pub fn set_cross_axis_gap(&mut self, gap: impl Into<Length>) {
+        self.options.cross_axis_gap = gap.into();
+        self.needs_relayout = true;
+    }
+
+    /// Get the padding of the container.
+    pub fn padding(&self) -> Sides<Length> {
+        self.options.padding
+    }
+
+    /// Set the space between the sides of the container and its items. Percentages are relative
+    /// to the size of the flexbox along the axis of the side, auto is the same as no padding. The
+    /// padding is part of the size the flexbox requires from its parent.
+    pub fn set_padding<T: Into<Length>>(&mut self, padding: Sides<T>) {
+        self.options.padding = padding.map(Into::into);
+        self.needs_relayout = true;
+    }
+
+    /// Whether the gaps are added to the sides of the container.
+    pub fn edge_gaps(&self) -> bool {
+        self.options.edge_gaps
+    }
+
+    /// Set whether the gaps are added to the sides of the container as well as between the items,
+    /// on top of the padding. The main axis gap is added to the main-start and main-end side, the
+    /// cross axis gap to the cross-start and cross-end side. Like the padding, these gaps are part
+    /// of the size the flexbox requires from its parent.
+    pub fn set_edge_gaps(&mut self, edge_gaps: bool) {
+        self.options.edge_gaps = edge_gaps;
         self.needs_relayout = true;
     }
 
@@ -896,6 +2252,70 @@ impl Flexbox {
         self.needs_relayout = true;
     }
 
+    /// Get the overflow alignment of justify-content.
+    pub fn justify_overflow(&self) -> OverflowAlignment {
+        self.options.justify_overflow
+    }
+
+    /// Set the overflow alignment of justify-content. With [OverflowAlignment::Safe], items that
+    /// overflow a main axis are packed against its start instead.
+    pub fn set_justify_overflow(&mut self, justify_overflow: OverflowAlignment) {
+        self.options.justify_overflow = justify_overflow;
+        self.needs_relayout = true;
+    }
+
+    /// Get the overflow alignment of align-items and align-content.
+    pub fn align_overflow(&self) -> OverflowAlignment {
+        self.options.align_overflow
+    }
+
+    /// Set the overflow alignment of align-items and align-content. With
+    /// [OverflowAlignment::Safe], items that overflow their main axis, or main axes that overflow
+    /// the container, are aligned to the cross-start side instead.
+    pub fn set_align_overflow(&mut self, align_overflow: OverflowAlignment) {
+        self.options.align_overflow = align_overflow;
+        self.needs_relayout = true;
+    }
+
+    /// Get the baseline fallback of the flexbox.
+    pub fn baseline_fallback(&self) -> BaselineFallback {
+        self.options.baseline_fallback
+    }
+
+    /// Set where the baseline of items without an explicit baseline is, when they are aligned on
+    /// their baseline. Use [BaselineFallback::FirstRow] to line up the first rows of text-like
+    /// views, like a label next to an input.
+    pub fn set_baseline_fallback(&mut self, baseline_fallback: BaselineFallback) {
+        self.options.baseline_fallback = baseline_fallback;
+        self.needs_relayout = true;
+    }
+
+    /// Get the sizing policy of the flexbox.
+    pub fn sizing_policy(&self) -> SizingPolicy {
+        self.options.sizing
+    }
+
+    /// Set the sizing policy of the flexbox. With [SizingPolicy::FitContent], the flexbox only
+    /// requests the space its items need from its parent, so it can be used in views that shrink
+    /// wrap their content.
+    pub fn set_sizing_policy(&mut self, sizing: SizingPolicy) {
+        self.options.sizing = sizing;
+        self.needs_relayout = true;
+    }
+
+    /// Get the rounding policy of the flexbox.
+    pub fn rounding_policy(&self) -> RoundingPolicy {
+        self.options.rounding
+    }
+
+    /// Set the rounding policy of the flexbox. This decides where the cells go that are left over
+    /// when space is divided over items with flex-grow and flex-shrink, auto margins,
+    /// justify-content, align-items and align-content.
+    pub fn set_rounding_policy(&mut self, rounding: RoundingPolicy) {
+        self.options.rounding = rounding;
+        self.needs_relayout = true;
+    }
+
     /// Get the flex-direction option.
     pub fn flex_direction(&self) -> FlexDirection {
         self.options.direction
@@ -918,26 +2338,364 @@ impl Flexbox {
         self.needs_relayout = true;
     }
 
-    /// Generate the concrete layout of this flexbox with the given constraints.
-    fn generate_layout(&self, constraints: XY<usize>) -> Layout<Rc<RefCell<FlexItem>>> {
-        let layout = FlexboxLayout::generate(
-            &self.content.iter().map(Rc::downgrade).collect::<Vec<_>>(),
+    /// Return the indices of the visible items in `content` in the order they appear visually.
+    /// Without a layout, this is the order of the items along the main axis.
+    fn visual_order(&self) -> Vec<usize> {
+        let position_in_content = |element: &Rc<RefCell<FlexItem>>| {
+            self.content
+                .iter()
+                .position(|item| Rc::ptr_eq(item, element))
+        };
+        let visual_order: Vec<usize> = if let Some(ref layout) = self.layout {
+            layout
+                .iter()
+                .filter_map(|placed_element| position_in_content(&placed_element.element))
+                .collect()
+        } else {
+            let mut visual_order: Vec<usize> = self
+                .ordered_content()
+                .filter_map(position_in_content)
+                .collect();
+            if self.options.direction.is_reverse() {
+                visual_order.reverse();
+            }
+            visual_order
+        };
+        // The layout is out of date until the next relayout if an item was hidden since.
+        visual_order
+            .into_iter()
+            .filter(|&index| {
+                RefCell::borrow(&self.content[index]).visibility == Visibility::Visible
+            })
+            .collect()
+    }
+
+    /// Move the focus to the next item in visual order that can take it, or the previous one if
+    /// `forward` is false. The item takes the focus coming from `source`. Returns
+    /// [EventResult::Ignored] if no item can take the focus.
+    fn move_focus(&mut self, forward: bool, source: Direction) -> EventResult {
+        let visual_order = self.visual_order();
+        let current = self
+            .focused
+            .and_then(|focused| visual_order.iter().position(|&index| index == focused));
+        let candidates: Vec<usize> = if forward {
+            visual_order[current.map_or(0, |current| current + 1)..].to_vec()
+        } else {
+            visual_order[..current.unwrap_or(visual_order.len())]
+                .iter()
+                .rev()
+                .copied()
+                .collect()
+        };
+        self.focus_first(candidates, source)
+            .unwrap_or(EventResult::Ignored)
+    }
+
+    /// Give the focus to the first item in `candidates` that can take it, coming from `source`.
+    /// Returns `None` if none of them can take the focus.
+    fn focus_first(
+        &mut self,
+        candidates: impl IntoIterator<Item = usize>,
+        source: Direction,
+    ) -> Option<EventResult> {
+        for index in candidates {
+            let take_focus = RefCell::borrow_mut(&self.content[index])
+                .view
+                .take_focus(source);
+            if let Ok(result) = take_focus {
+                return Some(result.and(self.set_focus(index)));
+            }
+        }
+        None
+    }
+
+    /// Return the position of the focused item and the line it is in, if it is in the layout.
+    fn focused_position(&self) -> Option<(Rect, Option<usize>)> {
+        let focused = &self.content[self.focused?];
+        self.layout
+            .as_ref()?
+            .iter()
+            .find(|placed_element| Rc::ptr_eq(&placed_element.element, focused))
+            .map(|placed_element| (placed_element.position, placed_element.line))
+    }
+
+    /// Move the focus to the nearest item in `direction` that can take it. Items that overlap the
+    /// focused item on the perpendicular axis come first, the nearest of those wins and ties go to
+    /// the one with the largest overlap. Without a focused item, the focus moves in visual order.
+    fn move_focus_spatially(&mut self, direction: Absolute) -> EventResult {
+        let source = Direction::Abs(direction.opposite());
+        let Some((current, _)) = self.focused_position() else {
+            return self.move_focus(
+                matches!(direction, Absolute::Right | Absolute::Down),
+                source,
+            );
+        };
+        let mut candidates = self
+            .layout
+            .iter()
+            .flatten()
+            .filter_map(|placed_element| {
+                let (distance, overlap, gap) =
+                    spatial_distance(current, placed_element.position, direction)?;
+                let index = self
+                    .content
+                    .iter()
+                    .position(|item| Rc::ptr_eq(item, &placed_element.element))?;
+                Some(((overlap == 0, distance, Reverse(overlap), gap), index))
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|&(score, _)| score);
+        self.focus_first(candidates.into_iter().map(|(_, index)| index), source)
+            .unwrap_or(EventResult::Ignored)
+    }
+
+    /// Move the focus to the first item in the line of the focused item that can take it, or the
+    /// last one if `first` is false.
+    fn move_focus_in_line(&mut self, first: bool) -> EventResult {
+        let Some((_, Some(line))) = self.focused_position() else {
+            return self.move_focus_to_line(first);
+        };
+        let mut candidates = self
+            .visual_order()
+            .into_iter()
+            .filter(|&index| self.line_of(index) == Some(line))
+            .collect::<Vec<_>>();
+        if !first {
+            candidates.reverse();
+        }
+        self.focus_first(candidates, Direction::none())
+            .unwrap_or(EventResult::Ignored)
+    }
+
+    /// Move the focus to the first line with an item that can take it, or the last line if
+    /// `first` is false. In that line, the item nearest to the focused item along the main axis
+    /// gets the focus.
+    fn move_focus_to_line(&mut self, first: bool) -> EventResult {
+        let current = self.focused_position().map(|(position, _)| position);
+        let lines = self
+            .layout
+            .iter()
+            .flatten()
+            .filter_map(|placed_element| placed_element.line)
+            .max()
+            .map_or(0, |last_line| last_line + 1);
+        let mut line_order = (0..lines).collect::<Vec<_>>();
+        if !first {
+            line_order.reverse();
+        }
+        let direction = self.options.direction;
+        let main_axis_span = move |position: Rect| match direction {
+            FlexDirection::Row | FlexDirection::RowReverse => (position.left(), position.right()),
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                (position.top(), position.bottom())
+            },
+        };
+        for line in line_order {
+            let mut candidates = self
+                .visual_order()
+                .into_iter()
+                .filter(|&index| self.line_of(index) == Some(line))
+                .collect::<Vec<_>>();
+            if !first {
+                candidates.reverse();
+            }
+            if let Some(current) = current {
+                candidates.sort_by_key(|&index| {
+                    let (overlap, gap) = span_distance(
+                        main_axis_span(current),
+                        main_axis_span(self.position_of(index).unwrap()),
+                    );
+                    (Reverse(overlap), gap)
+                });
+            }
+            if let Some(result) = self.focus_first(candidates, Direction::none()) {
+                return result;
+            }
+        }
+        EventResult::Ignored
+    }
+
+    /// Return the line of the item at `index` in `content`, if it is in a line of the layout.
+    fn line_of(&self, index: usize) -> Option<usize> {
+        self.placed_element(index)?.line
+    }
+
+    /// Return the position of the item at `index` in `content`, if it is in the layout.
+    fn position_of(&self, index: usize) -> Option<Rect> {
+        Some(self.placed_element(index)?.position)
+    }
+
+    /// Return the placed element of the item at `index` in `content`, if it is in the layout.
+    fn placed_element(&self, index: usize) -> Option<&PlacedElement<Rc<RefCell<FlexItem>>>> {
+        self.layout
+            .as_ref()?
+            .iter()
+            .find(|placed_element| Rc::ptr_eq(&placed_element.element, &self.content[index]))
+    }
+
+    /// Give the focus to the item at `index`, telling the item that had it that it lost the focus.
+    fn set_focus(&mut self, index: usize) -> EventResult {
+        match self.focused.replace(index) {
+            Some(focused) if focused != index => RefCell::borrow_mut(&self.content[focused])
+                .view
+                .on_event(Event::FocusLost),
+            _ => EventResult::Ignored,
+        }
+    }
+
+    /// Give the focus to the next item after the item at `index` in visual order that can take it, or the previous one if there is no such item. Returns the index of the item that
+    /// took the focus, if any.
+    fn focus_neighbour(&mut self, index: usize) -> Option<usize> {
+        let visual_order = self.visual_order();
+        let position = visual_order
+            .iter()
+            .position(|&other| other == index)
+            .unwrap_or(visual_order.len());
+        let next = visual_order[position..]
+            .iter()
+            .map(|&other| (other, Direction::front()));
+        let previous = visual_order[..position]
+            .iter()
+            .rev()
+            .map(|&other| (other, Direction::back()));
+        next.chain(previous)
+            .find(|&(other, source)| {
+                other != index
+                    && RefCell::borrow_mut(&self.content[other])
+                        .view
+                        .take_focus(source)
+                        .is_ok()
+            })
+            .map(|(other, _)| other)
+    }
+
+    /// Return the items in `content` sorted by their order.
+    fn ordered_content(&self) -> impl Iterator<Item = &Rc<RefCell<FlexItem>>> {
+        // Sorting is stable, so items with the same order keep their order in `content`.
+        let mut ordered_content = self.content.iter().collect::<Vec<_>>();
+        ordered_content.sort_by_key(|item| RefCell::borrow(item).order);
+        ordered_content.into_iter()
+    }
+
+    /// Return the window of an absolutely positioned item in a flexbox with size `constraints`.
+    fn absolute_window(&self, item: &mut FlexItem, constraints: XY<usize>) -> Window {
+        let edge_space = self.options.edge_space(constraints);
+        let required_size = item.view.required_size(constraints);
+        let (left, width) = absolute_span(
+            (item.inset.left, item.inset.right),
+            (item.margin.left, item.margin.right),
+            (item.min_size.x, item.max_size.x),
+            required_size.x,
+            edge_space.left,
             constraints.x,
+        );
+        let (top, height) = absolute_span(
+            (item.inset.top, item.inset.bottom),
+            (item.margin.top, item.margin.bottom),
+            (item.min_size.y, item.max_size.y),
+            required_size.y,
+            edge_space.top,
             constraints.y,
+        );
+        Window::from_size((left, top), (width, height))
+    }
+
+    /// Generate the layout of the items for a flexbox with size `constraints`. The items are layed
+    /// out in the area inside of the padding.
+    fn generate_flexbox_layout(&self, constraints: XY<usize>) -> Rc<RefCell<FlexboxLayout>> {
+        let ordered_content = self
+            .ordered_content()
+            .filter(|item| {
+                let item = RefCell::borrow(item);
+                item.position == Position::Static && item.visibility != Visibility::Collapsed
+            })
+            .map(Rc::downgrade)
+            .collect::<Vec<_>>();
+        let edge_space = self.options.edge_space(constraints);
+        FlexboxLayout::generate(
+            &ordered_content,
+            constraints
+                .x
+                .saturating_sub(edge_space.left + edge_space.right),
+            constraints
+                .y
+                .saturating_sub(edge_space.top + edge_space.bottom),
             self.options,
+        )
+    }
+
+    /// Return the window of the view of `item` inside of `slot`, the window that is assigned to
+    /// the item.
+    fn content_window(&self, item: &mut FlexItem, slot: Window) -> Window {
+        let required_size = item.view.required_size(slot.size).or_min(slot.size);
+        let rounding = self.options.rounding;
+        let placed = item.placement.zip3(slot.size, required_size).map(
+            |(placement, slot_size, required_size)| {
+                let free_space = slot_size - required_size;
+                match placement {
+                    Placement::Stretch => (0, slot_size),
+                    Placement::Start => (0, required_size),
+                    Placement::Center => (
+                        rounding.distribute(free_space, 2)[0] as isize,
+                        required_size,
+                    ),
+                    Placement::End => (free_space as isize, required_size),
+                }
+            },
         );
+        slot.offset(placed.map(|(offset, _)| offset))
+            .resized(placed.map(|(_, size)| size))
+    }
+
+    /// Generate the concrete layout of this flexbox with the given constraints. Items that aren't
+    /// visible are left out, so they aren't drawn and can't receive focus or mouse events.
+    fn generate_layout(&self, constraints: XY<usize>) -> Layout<Rc<RefCell<FlexItem>>> {
+        let edge_space = self.options.edge_space(constraints);
+        let layout = self.generate_flexbox_layout(constraints);
+        let windows = RefCell::borrow_mut(&layout).windows();
+        let mut slots = windows
+            .into_iter()
+            .map(|(element, window)| {
+                let line = RefCell::borrow(&layout).main_axis_index(&element);
+                (
+                    element,
+                    window.offset((edge_space.left as isize, edge_space.top as isize)),
+                    line,
+                )
+            })
+            .collect::<Vec<_>>();
+        for element in self.ordered_content() {
+            let mut item = RefCell::borrow_mut(element);
+            if item.position == Position::Absolute {
+                slots.push((
+                    Rc::clone(element),
+                    self.absolute_window(&mut item, constraints),
+                    None,
+                ));
+            }
+        }
+
         let mut result = Layout {
             elements: Vec::new(),
         };
-        RefCell::borrow_mut(&layout)
-            .windows()
-            .into_iter()
-            .for_each(|item| {
-                result.elements.push(PlacedElement {
-                    element: item.0,
-                    position: item.1,
-                })
+        for (element, slot, line) in slots {
+            let mut item = RefCell::borrow_mut(&element);
+            if item.visibility != Visibility::Visible {
+                continue;
+            }
+            let (position, _) = slot.clip();
+            let (content_position, content_offset) = self.content_window(&mut item, slot).clip();
+            let z_index = item.z_index;
+            drop(item);
+            result.elements.push(PlacedElement {
+                element,
+                position,
+                content_position,
+                content_offset,
+                z_index,
+                line,
             });
+        }
         result
     }
 }
@@ -946,10 +2704,17 @@ impl View for Flexbox {
     /// Draw this view using the printer.
     fn draw(&self, printer: &cursive_core::Printer<'_, '_>) {
         if let Some(ref layout) = self.layout {
-            for placed_element in layout {
-                RefCell::borrow(&placed_element.element)
-                    .view
-                    .draw(&printer.windowed(placed_element.position));
+            for placed_element in layout.stacking_order() {
+                let focused = self.focused.is_some_and(|focused| {
+                    Rc::ptr_eq(&self.content[focused], &placed_element.element)
+                });
+                RefCell::borrow(&placed_element.element).view.draw(
+                    &printer
+                        .focused(focused)
+                        .windowed(placed_element.content_position)
+                        .content_offset(placed_element.content_offset)
+                        .inner_size(placed_element.size()),
+                );
             }
         }
     }
@@ -965,7 +2730,7 @@ impl View for Flexbox {
         for placed_element in self.layout.as_ref().unwrap() {
             RefCell::borrow_mut(&placed_element.element)
                 .view
-                .layout(placed_element.position.size());
+                .layout(placed_element.size());
         }
 
         self.needs_relayout = false;
@@ -983,9 +2748,22 @@ impl View for Flexbox {
     /// Given `constraint`, return the minimal required size the printer for this view should be.
     /// `constraint` is the maximum possible size for the printer.
     fn required_size(&mut self, constraint: cursive_core::Vec2) -> cursive_core::Vec2 {
-        // PERF: Cache the values that the previous layout was generated with and regenerate if
-        // cached version is outdated.
-        constraint
+        match self.options.sizing {
+            SizingPolicy::Fill => constraint,
+            SizingPolicy::FitContent => {
+                // PERF: Cache the values that the previous layout was generated with and
+                // regenerate if cached version is outdated.
+                let edge_space = self.options.edge_space(constraint);
+                let content_size =
+                    RefCell::borrow(&self.generate_flexbox_layout(constraint)).content_size();
+                (content_size
+                    + XY::from((
+                        edge_space.left + edge_space.right,
+                        edge_space.top + edge_space.bottom,
+                    )))
+                .or_min(constraint)
+            },
+        }
     }
 
     fn on_event(
@@ -1002,7 +2780,9 @@ impl View for Flexbox {
                 if let Some(placed_element) =
                     layout.element_at(global_to_view_coordinates(*position, *offset))
                 {
-                    *offset = *offset + placed_element.position.top_left();
+                    *offset = *offset + placed_element.content_position.top_left();
+                    // The part of the view that is cut off is still part of its coordinates.
+                    *position = *position + placed_element.content_offset;
                     RefCell::borrow_mut(&placed_element.element)
                         .view
                         .on_event(event)
@@ -1012,12 +2792,31 @@ impl View for Flexbox {
             } else {
                 EventResult::Ignored
             }
-        } else if let Some(active_child) = self.focused {
-            RefCell::borrow_mut(&self.content[active_child])
-                .view
-                .on_event(event)
         } else {
-            EventResult::Ignored
+            let result = if let Some(active_child) = self.focused {
+                RefCell::borrow_mut(&self.content[active_child])
+                    .view
+                    .on_event(event.clone())
+            } else {
+                EventResult::Ignored
+            };
+            // Navigation keys move the focus when the focused item doesn't use them itself.
+            match result {
+                EventResult::Ignored => match event {
+                    Event::Key(Key::Tab) => self.move_focus(true, Direction::front()),
+                    Event::Shift(Key::Tab) => self.move_focus(false, Direction::back()),
+                    Event::Key(Key::Right) => self.move_focus_spatially(Absolute::Right),
+                    Event::Key(Key::Down) => self.move_focus_spatially(Absolute::Down),
+                    Event::Key(Key::Left) => self.move_focus_spatially(Absolute::Left),
+                    Event::Key(Key::Up) => self.move_focus_spatially(Absolute::Up),
+                    Event::Key(Key::Home) => self.move_focus_in_line(true),
+                    Event::Key(Key::End) => self.move_focus_in_line(false),
+                    Event::Key(Key::PageUp) => self.move_focus_to_line(true),
+                    Event::Key(Key::PageDown) => self.move_focus_to_line(false),
+                    _ => EventResult::Ignored,
+                },
+                result => result,
+            }
         }
     }
 
@@ -1025,8 +2824,11 @@ impl View for Flexbox {
         &mut self,
         selector: &cursive_core::view::Selector<'_>,
     ) -> Result<EventResult, cursive_core::view::ViewNotFound> {
-        for (index, view) in self.content.iter_mut().enumerate() {
-            if let Ok(event_result) = RefCell::borrow_mut(view).view.focus_view(selector) {
+        for index in self.visual_order() {
+            if let Ok(event_result) = RefCell::borrow_mut(&self.content[index])
+                .view
+                .focus_view(selector)
+            {
                 self.focused = Some(index);
                 return Ok(event_result);
             }
@@ -1046,17 +2848,48 @@ impl View for Flexbox {
         }
     }
 
+    /// Give the focus to an item. Coming from the front, the left or the top, the first item in
+    /// visual order that can take the focus gets it. Coming from the back, the right or the
+    /// bottom, the last one does. Without a direction, the item that had the focus last gets it
+    /// back if it can still take it.
     fn take_focus(
         &mut self,
-        _source: cursive_core::direction::Direction,
+        source: cursive_core::direction::Direction,
     ) -> Result<EventResult, cursive_core::view::CannotFocus> {
-        Ok(EventResult::Consumed(None))
+        let mut candidates = self.visual_order();
+        match source {
+            Direction::Rel(Relative::Back) | Direction::Abs(Absolute::Right | Absolute::Down) => {
+                candidates.reverse()
+            },
+            Direction::Abs(Absolute::None) => {
+                if let Some(focused) = self.focused {
+                    candidates.retain(|&index| index != focused);
+                    candidates.insert(0, focused);
+                }
+            },
+            Direction::Rel(Relative::Front) | Direction::Abs(Absolute::Left | Absolute::Up) => {},
+        }
+        for index in candidates {
+            let take_focus = RefCell::borrow_mut(&self.content[index])
+                .view
+                .take_focus(source);
+            if let Ok(result) = take_focus {
+                // The flexbox didn't have the focus, so no item needs to be told it lost it.
+                self.focused = Some(index);
+                return Ok(result);
+            }
+        }
+        Err(cursive_core::view::CannotFocus)
     }
 
     fn important_area(&self, _view_size: Vec2) -> Rect {
         if let Some(ref layout) = self.layout {
-            if let Some(focused) = self.focused {
-                layout.elements[focused].position
+            if let Some(placed_element) = self.focused.and_then(|focused| {
+                layout
+                    .iter()
+                    .find(|element| Rc::ptr_eq(&element.element, &self.content[focused]))
+            }) {
+                placed_element.position
             } else {
                 Rect::from_size((0, 0), (1, 1))
             }
@@ -1068,33 +2901,307 @@ impl View for Flexbox {
 
 impl FlexItem {
     /// Create a flex item with the given grow factor.
-    pub fn with_flex_grow(view: impl IntoBoxedView, flex_grow: u8) -> Self {
+    pub fn with_flex_grow(view: impl IntoBoxedView, flex_grow: impl Into<f64>) -> Self {
         Self {
             view: view.into_boxed_view(),
-            flex_grow,
+            flex_grow: finite_flex_factor(flex_grow.into()),
+            flex_shrink: 1.0,
+            flex_basis: FlexBasis::default(),
+            min_size: XY::new(None, None),
+            max_size: XY::new(None, None),
+            align_self: AlignSelf::default(),
+            order: 0,
+            margin: Sides::default(),
+            baseline: None,
+            aspect_ratio: None,
+            position: Position::default(),
+            inset: Sides::all(Length::Auto),
+            z_index: 0,
+            visibility: Visibility::default(),
+            placement: XY::new(Placement::default(), Placement::default()),
         }
     }
 
-    /// Set the flex-grow.
-    pub fn set_flex_grow(&mut self, flex_grow: u8) {
-        self.flex_grow = flex_grow;
+    /// Set the flex-grow. Infinite or NaN factors are set to 0.
+    pub fn set_flex_grow(&mut self, flex_grow: impl Into<f64>) {
+        self.flex_grow = finite_flex_factor(flex_grow.into());
     }
 
     /// Returns the flex-grow.
-    pub fn flex_grow(&self) -> u8 {
+    pub fn flex_grow(&self) -> f64 {
         self.flex_grow
     }
+
+    /// Set the flex-shrink. Infinite or NaN factors are set to 0.
+    pub fn set_flex_shrink(&mut self, flex_shrink: impl Into<f64>) {
+        self.flex_shrink = finite_flex_factor(flex_shrink.into());
+    }
+
+    /// Returns the flex-shrink.
+    pub fn flex_shrink(&self) -> f64 {
+        self.flex_shrink
+    }
+
+    /// Set the flex-basis.
+    pub fn set_flex_basis(&mut self, flex_basis: FlexBasis) {
+        self.flex_basis = flex_basis;
+    }
+
+    /// Returns the flex-basis.
+    pub fn flex_basis(&self) -> FlexBasis {
+        self.flex_basis
+    }
+
+    /// Set the minimum size along each axis. `None` means there is no minimum.
+    pub fn set_min_size<T: Into<Length>>(&mut self, min_size: XY<Option<T>>) {
+        self.min_size = min_size.map(|length| length.map(Into::into));
+    }
+
+    /// Returns the minimum size along each axis.
+    pub fn min_size(&self) -> XY<Option<Length>> {
+        self.min_size
+    }
+
+    /// Set the maximum size along each axis. `None` means there is no maximum.
+    pub fn set_max_size<T: Into<Length>>(&mut self, max_size: XY<Option<T>>) {
+        self.max_size = max_size.map(|length| length.map(Into::into));
+    }
+
+    /// Returns the maximum size along each axis.
+    pub fn max_size(&self) -> XY<Option<Length>> {
+        self.max_size
+    }
+
+    /// Set the align-self.
+    pub fn set_align_self(&mut self, align_self: AlignSelf) {
+        self.align_self = align_self;
+    }
+
+    /// Returns the align-self.
+    pub fn align_self(&self) -> AlignSelf {
+        self.align_self
+    }
+
+    /// Set the order.
+    pub fn set_order(&mut self, order: i32) {
+        self.order = order;
+    }
+
+    /// Returns the order.
+    pub fn order(&self) -> i32 {
+        self.order
+    }
+
+    /// Set the margin.
+    pub fn set_margin<T: Into<Length>>(&mut self, margin: Sides<T>) {
+        self.margin = margin.map(Into::into);
+    }
+
+    /// Returns the margin.
+    pub fn margin(&self) -> Sides<Length> {
+        self.margin
+    }
+
+    /// Set the baseline, the offset of the first line of text from the cross-start side of the
+    /// item. Set to `None` to use the baseline fallback of the flexbox.
+    pub fn set_baseline(&mut self, baseline: Option<usize>) {
+        self.baseline = baseline;
+    }
+
+    /// Returns the baseline, if set.
+    pub fn baseline(&self) -> Option<usize> {
+        self.baseline
+    }
+
+    /// Set the aspect ratio, the width divided by the height as it appears on the screen. Set to
+    /// `None` to size both axes independently.
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: Option<f64>) {
+        self.aspect_ratio = aspect_ratio;
+    }
+
+    /// Returns the aspect ratio, if set.
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        self.aspect_ratio
+    }
+
+    /// Set the position.
+    pub fn set_position(&mut self, position: Position) {
+        self.position = position;
+    }
+
+    /// Returns the position.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Set the inset, the offsets from the edges of the flexbox when absolutely positioned.
+    pub fn set_inset<T: Into<Length>>(&mut self, inset: Sides<T>) {
+        self.inset = inset.map(Into::into);
+    }
+
+    /// Returns the inset.
+    pub fn inset(&self) -> Sides<Length> {
+        self.inset
+    }
+
+    /// Set the z-index.
+    pub fn set_z_index(&mut self, z_index: i32) {
+        self.z_index = z_index;
+    }
+
+    /// Returns the z-index.
+    pub fn z_index(&self) -> i32 {
+        self.z_index
+    }
+
+    /// Set the visibility.
+    pub fn set_visibility(&mut self, visibility: Visibility) {
+        self.visibility = visibility;
+    }
+
+    /// Returns the visibility.
+    pub fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+
+    /// Set the placement of the view inside of the space of this item.
+    pub fn set_placement(&mut self, placement: XY<Placement>) {
+        self.placement = placement;
+    }
+
+    /// Returns the placement of the view inside of the space of this item.
+    pub fn placement(&self) -> XY<Placement> {
+        self.placement
+    }
 }
 
 impl<T: IntoBoxedView> From<T> for FlexItem {
     fn from(value: T) -> Self {
         Self {
             view: value.into_boxed_view(),
-            flex_grow: 0,
+            flex_grow: 0.0,
+            flex_shrink: 1.0,
+            flex_basis: FlexBasis::default(),
+            min_size: XY::new(None, None),
+            max_size: XY::new(None, None),
+            align_self: AlignSelf::default(),
+            order: 0,
+            margin: Sides::default(),
+            baseline: None,
+            aspect_ratio: None,
+            position: Position::default(),
+            inset: Sides::all(Length::Auto),
+            z_index: 0,
+            visibility: Visibility::default(),
+            placement: XY::new(Placement::default(), Placement::default()),
         }
     }
 }
 
+/// Return how far `to` lies from `from` in `direction`, or `None` if it doesn't lie entirely in
+/// that direction. Returns the distance between the facing sides, and the overlap and the distance
+/// between the rectangles along the perpendicular axis.
+fn spatial_distance(from: Rect, to: Rect, direction: Absolute) -> Option<(usize, usize, usize)> {
+    let (distance, from_span, to_span) = match direction {
+        Absolute::Left => (
+            from.left().checked_sub(to.right() + 1)?,
+            (from.top(), from.bottom()),
+            (to.top(), to.bottom()),
+        ),
+        Absolute::Right => (
+            to.left().checked_sub(from.right() + 1)?,
+            (from.top(), from.bottom()),
+            (to.top(), to.bottom()),
+        ),
+        Absolute::Up => (
+            from.top().checked_sub(to.bottom() + 1)?,
+            (from.left(), from.right()),
+            (to.left(), to.right()),
+        ),
+        Absolute::Down => (
+            to.top().checked_sub(from.bottom() + 1)?,
+            (from.left(), from.right()),
+            (to.left(), to.right()),
+        ),
+        Absolute::None => return None,
+    };
+    let (overlap, gap) = span_distance(from_span, to_span);
+    Some((distance, overlap, gap))
+}
+
+/// Return the overlap and the distance between two spans of cells, given by their first and last
+/// cell.
+fn span_distance(from: (usize, usize), to: (usize, usize)) -> (usize, usize) {
+    let overlap = (from.1.min(to.1) + 1).saturating_sub(from.0.max(to.0));
+    let gap =
+        to.0.saturating_sub(from.1 + 1)
+            .max(from.0.saturating_sub(to.1 + 1));
+    (overlap, gap)
+}
+
+/// Return the start and size of an absolutely positioned item along one axis of a container with
+/// `container_size`. `static_start` is where the item starts if neither inset on the axis is set.
+fn absolute_span(
+    (inset_start, inset_end): (Length, Length),
+    (margin_start, margin_end): (Length, Length),
+    (minimum_size, maximum_size): (Option<Length>, Option<Length>),
+    required_size: usize,
+    static_start: usize,
+    container_size: usize,
+) -> (isize, usize) {
+    let inset_start = inset_start.resolve(container_size);
+    let inset_end = inset_end.resolve(container_size);
+    let margin_start = margin_start.resolve(container_size).cells();
+    let margin_end = margin_end.resolve(container_size).cells();
+    let (minimum_size, maximum_size) = resolve_limits(minimum_size, maximum_size, container_size);
+    let size = if inset_start != Length::Auto && inset_end != Length::Auto {
+        container_size
+            .saturating_sub(inset_start.cells() + margin_start + margin_end + inset_end.cells())
+    } else {
+        required_size
+    };
+    let size = clamp_size(size, minimum_size, maximum_size);
+    let start = match (inset_start, inset_end) {
+        (Length::Auto, Length::Auto) => (static_start + margin_start) as isize,
+        (Length::Auto, inset_end) => {
+            container_size as isize - (inset_end.cells() + margin_end + size) as isize
+        },
+        (inset_start, _) => (inset_start.cells() + margin_start) as isize,
+    };
+    (start, size)
+}
+
+/// Return the minimum and maximum size in cells, with percentages relative to `container_size`.
+/// Without a minimum size, or with an auto minimum size, the minimum size is 0.
+fn resolve_limits(
+    minimum_size: Option<Length>,
+    maximum_size: Option<Length>,
+    container_size: usize,
+) -> (usize, Option<usize>) {
+    let minimum_size = minimum_size.map_or(0, |length| length.resolve(container_size).cells());
+    let maximum_size = match maximum_size.map(|length| length.resolve(container_size)) {
+        Some(Length::Auto) | None => None,
+        Some(length) => Some(length.cells()),
+    };
+    (minimum_size, maximum_size)
+}
+
+/// Return `flex_factor`, or 0 if it's infinite or NaN. Such factors can't be distributed.
+fn finite_flex_factor(flex_factor: f64) -> f64 {
+    if flex_factor.is_finite() {
+        flex_factor
+    } else {
+        0.0
+    }
+}
+
+/// Clamp `size` between `minimum_size` and `maximum_size`. The minimum wins if the maximum is
+/// smaller than the minimum.
+fn clamp_size(size: usize, minimum_size: usize, maximum_size: Option<usize>) -> usize {
+    size.min(maximum_size.unwrap_or(usize::MAX))
+        .max(minimum_size)
+}
+
 /// Convert `global_coordinates` to coordinates within a View, using `view_offset` as the top-left
 /// point of the view to convert to.
 fn global_to_view_coordinates(global_coordinates: XY<usize>, view_offset: XY<usize>) -> XY<usize> {
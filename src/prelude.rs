@@ -1 +1,5 @@
-pub use crate::{AlignContent, AlignItems, FlexDirection, FlexWrap, Flexbox, JustifyContent};
+pub use crate::{
+    AlignContent, AlignItems, AlignSelf, BaselineFallback, FlexBasis, FlexDirection, FlexWrap,
+    Flexbox, JustifyContent, Length, OverflowAlignment, Placement, Position, RoundingPolicy, Sides,
+    SizingPolicy, Visibility,
+};
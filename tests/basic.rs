@@ -1,10 +1,16 @@
 use crossbeam::channel::{Receiver, Sender};
 use cursive::backends::puppet::observed::ObservedScreen;
 use cursive::backends::puppet::Backend;
-use cursive::event::Event;
-use cursive::views::{Panel, TextView};
+use cursive::direction::Direction;
+use cursive::event::{Event, Key, MouseButton, MouseEvent};
+use cursive::view::{IntoBoxedView, Nameable, View};
+use cursive::views::{Button, EditView, LinearLayout, Panel, TextContent, TextView};
 use cursive::XY;
-use cursive_flexbox::{AlignContent, AlignItems, FlexDirection, FlexWrap, Flexbox, JustifyContent};
+use cursive_flexbox::{
+    AlignContent, AlignItems, AlignSelf, BaselineFallback, FlexBasis, FlexDirection, FlexWrap,
+    Flexbox, JustifyContent, Length, OverflowAlignment, Placement, Position, RoundingPolicy, Sides,
+    SizingPolicy, Visibility,
+};
 use insta::assert_snapshot;
 
 // The TestCursive code below was copied and altered from deinstabpel/cursive-tabs.
@@ -849,6 +855,26 @@ fn test_main_axis_gap() {
     assert_snapshot!(tsiv.last_screen());
 }
 
+#[test]
+fn test_gap_u32() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+            ]);
+            let gap: u32 = 1;
+            flexbox.set_flex_wrap(FlexWrap::Wrap);
+            flexbox.set_main_axis_gap(gap);
+            flexbox.set_cross_axis_gap(gap);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (12, 4),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
 #[test]
 fn test_main_axis_gap_justify_center() {
     let mut tsiv = TestCursive::new_with_size(
@@ -884,3 +910,1643 @@ fn test_main_axis_gap_justify_space_evenly() {
     );
     assert_snapshot!(tsiv.last_screen());
 }
+
+#[test]
+fn test_flex_direction_row_reverse() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+            ]);
+            flexbox.set_flex_direction(FlexDirection::RowReverse);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_direction_row_reverse_justify_flexend() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+            ]);
+            flexbox.set_flex_direction(FlexDirection::RowReverse);
+            flexbox.set_justify_content(JustifyContent::FlexEnd);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_direction_row_reverse_wrap() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+                TextView::new("|Dog|"),
+                TextView::new("|Elk|"),
+            ]);
+            flexbox.set_flex_direction(FlexDirection::RowReverse);
+            flexbox.set_flex_wrap(FlexWrap::Wrap);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_direction_column_reverse() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+            ]);
+            flexbox.set_flex_direction(FlexDirection::ColumnReverse);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (10, 5),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_direction_column_reverse_wrap() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+                TextView::new("|Dog|"),
+                TextView::new("|Elk|"),
+            ]);
+            flexbox.set_flex_direction(FlexDirection::ColumnReverse);
+            flexbox.set_flex_wrap(FlexWrap::Wrap);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (12, 5),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_direction_row_reverse_mouse() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let content_bat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |_| content_ape.set_content("ape")).into_boxed_view(),
+                Button::new_raw("Bat", move |_| content_bat.set_content("bat")).into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+            ]);
+            flexbox.set_flex_direction(FlexDirection::RowReverse);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 1),
+    );
+    // Items are placed as "none Bat Ape" against the right side, click on "Bat".
+    tsiv.input(Event::Mouse {
+        offset: XY::new(0, 0),
+        position: XY::new(14, 0),
+        event: MouseEvent::Release(MouseButton::Left),
+    });
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_shrink() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let flexbox = Flexbox::from(vec![
+                TextView::new("|Aardvark|").no_wrap(),
+                TextView::new("|Bat|").no_wrap(),
+                TextView::new("|Chameleon|").no_wrap(),
+            ]);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_shrink_factors() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Aardvark|").no_wrap(),
+                TextView::new("|Bat|").no_wrap(),
+                TextView::new("|Chameleon|").no_wrap(),
+            ]);
+            flexbox.set_flex_shrink(0, 0);
+            flexbox.set_flex_shrink(2, 2);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_basis_zero_equal_columns() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Chameleon")),
+                Panel::new(TextView::new("Gnu")),
+            ]);
+            for index in 0..flexbox.len() {
+                flexbox.set_flex_basis(index, FlexBasis::Zero);
+                flexbox.set_flex_grow(index, 1);
+            }
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (30, 4),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_basis_cells() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+            ]);
+            flexbox.set_flex_basis(0, FlexBasis::Cells(12));
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_basis_percent() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+            ]);
+            flexbox.set_flex_basis(0, FlexBasis::Percent(50.0));
+            flexbox.set_flex_basis(1, FlexBasis::Percent(25.0));
+            flexbox.set_justify_content(JustifyContent::FlexEnd);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_max_size_grow() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Side")),
+                Panel::new(TextView::new("Main")),
+                Panel::new(TextView::new("Info")),
+            ]);
+            for index in 0..flexbox.len() {
+                flexbox.set_flex_grow(index, 1);
+            }
+            flexbox.set_max_size(0, XY::new(Some(8), None));
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (30, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_min_size_shrink() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Aardvark|").no_wrap(),
+                TextView::new("|Bat|").no_wrap(),
+                TextView::new("|Chameleon|").no_wrap(),
+            ]);
+            flexbox.set_min_size(0, XY::new(Some(10), None));
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_cross_axis_size_limits() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+                Panel::new(TextView::new("Dog\nEwe\nFrog")),
+            ]);
+            flexbox.set_max_size(0, XY::new(None, Some(3)));
+            flexbox.set_min_size(1, XY::new(Some(7), Some(4)));
+            flexbox.set_align_items(AlignItems::Stretch);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 6),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_align_self() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+                Panel::new(TextView::new("Dog\nEwe\nFrog")),
+                Panel::new(TextView::new("Gnu")),
+            ]);
+            flexbox.set_align_items(AlignItems::Stretch);
+            flexbox.set_align_self(0, AlignSelf::Center);
+            flexbox.set_align_self(1, AlignSelf::FlexEnd);
+            flexbox.set_align_self(3, AlignSelf::Auto);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (25, 6),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_align_self_stretch_column() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+                Panel::new(TextView::new("Chameleon")),
+            ]);
+            flexbox.set_flex_direction(FlexDirection::Column);
+            flexbox.set_align_items(AlignItems::Center);
+            flexbox.set_align_self(1, AlignSelf::Stretch);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (15, 9),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_order() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+                TextView::new("|Dog|"),
+            ]);
+            flexbox.set_order(0, 1);
+            flexbox.set_order(2, -1);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (25, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_order_row_reverse() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+                TextView::new("|Dog|"),
+            ]);
+            flexbox.set_flex_direction(FlexDirection::RowReverse);
+            flexbox.set_order(0, 1);
+            flexbox.set_order(2, -1);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (25, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_margin_auto_main_axis() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("File"),
+                TextView::new("Edit"),
+                TextView::new("Help"),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            flexbox.set_margin(
+                2,
+                Sides {
+                    left: Length::Auto,
+                    ..Default::default()
+                },
+            );
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 1),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_margin_fixed() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+                Panel::new(TextView::new("Cat")),
+            ]);
+            flexbox.set_margin(1, Sides::symmetric(Length::Cells(1), Length::Cells(2)));
+            flexbox.set_align_items(AlignItems::FlexStart);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 5),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_margin_auto_cross_axis() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+                Panel::new(TextView::new("Dog\nEwe\nFrog\nGnu\nHen")),
+            ]);
+            flexbox.set_margin(0, Sides::all(Length::Auto));
+            flexbox.set_margin(
+                1,
+                Sides {
+                    top: Length::Auto,
+                    ..Default::default()
+                },
+            );
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 7),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_margin_row_reverse() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+            ]);
+            flexbox.set_flex_direction(FlexDirection::RowReverse);
+            flexbox.set_margin(
+                1,
+                Sides {
+                    left: Length::Cells(3),
+                    ..Default::default()
+                },
+            );
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 1),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_padding() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+            ]);
+            flexbox.set_flex_grow(1, 1);
+            flexbox.set_padding(Sides::new(1, 2, 0, 3));
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 5),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_padding_required_size() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![TextView::new("Ape")]);
+            flexbox.set_sizing_policy(SizingPolicy::FitContent);
+            flexbox.set_main_axis_gap(1);
+            flexbox.set_padding(Sides::new(1, 2, 0, 3));
+            flexbox.set_edge_gaps(true);
+            siv.add_fullscreen_layer(
+                LinearLayout::horizontal()
+                    .child(flexbox)
+                    .child(TextView::new("Bat")),
+            );
+        },
+        (20, 2),
+    );
+    // The flexbox requires its padding and edge gaps, so "Bat" starts after 3 + 1 + 3 + 1 + 2
+    // columns.
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_edge_gaps() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+                Panel::new(TextView::new("Cat")),
+                Panel::new(TextView::new("Dog")),
+            ]);
+            for index in 0..flexbox.len() {
+                flexbox.set_flex_grow(index, 1);
+            }
+            flexbox.set_flex_wrap(FlexWrap::Wrap);
+            flexbox.set_main_axis_gap(2);
+            flexbox.set_cross_axis_gap(1);
+            flexbox.set_edge_gaps(true);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (16, 9),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_percent_gap_and_padding() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+            ]);
+            for index in 0..flexbox.len() {
+                flexbox.set_flex_grow(index, 1);
+            }
+            flexbox.set_main_axis_gap(Length::Percent(10.0));
+            flexbox.set_padding(Sides::symmetric(Length::Cells(1), Length::Percent(10.0)));
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (30, 5),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_percent_max_size() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Side")),
+                Panel::new(TextView::new("Main")),
+            ]);
+            for index in 0..flexbox.len() {
+                flexbox.set_flex_grow(index, 1);
+            }
+            flexbox.set_max_size(0, XY::new(Some(Length::Percent(25.0)), None));
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (40, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_padding_mouse() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let content_bat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |_| content_ape.set_content("ape")).into_boxed_view(),
+                Button::new_raw("Bat", move |_| content_bat.set_content("bat")).into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            flexbox.set_padding(Sides::new(1, 0, 0, 4));
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 2),
+    );
+    // Items are placed as "Ape Bat none" after the padding, click on "Bat".
+    tsiv.input(Event::Mouse {
+        offset: XY::new(0, 0),
+        position: XY::new(9, 1),
+        event: MouseEvent::Release(MouseButton::Left),
+    });
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_align_content_space_evenly() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let mut flexbox = Flexbox::from(vec![
+            TextView::new("|Ape|"),
+            TextView::new("|Bat|"),
+            TextView::new("|Cat|"),
+            TextView::new("|Dog|"),
+            TextView::new("|Elk|"),
+            TextView::new("|Fly|"),
+            TextView::new("|Gnu|"),
+        ]);
+        flexbox.set_flex_wrap(FlexWrap::Wrap);
+        flexbox.set_align_content(AlignContent::SpaceEvenly);
+        siv.add_fullscreen_layer(flexbox);
+    });
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_align_content_space_between_single_axis() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![TextView::new("|Ape|"), TextView::new("|Bat|")]);
+            flexbox.set_flex_wrap(FlexWrap::Wrap);
+            flexbox.set_align_content(AlignContent::SpaceBetween);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_justify_content_start_row_reverse() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+            ]);
+            flexbox.set_flex_direction(FlexDirection::RowReverse);
+            flexbox.set_justify_content(JustifyContent::Start);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_justify_content_right() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+            ]);
+            flexbox.set_justify_content(JustifyContent::Right);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_justify_content_center_overflow_unsafe() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox =
+                Flexbox::from(vec![TextView::new("|Aardvark-Buffalo-Cat|").no_wrap()]);
+            flexbox.set_flex_shrink(0, 0);
+            flexbox.set_justify_content(JustifyContent::Center);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (15, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_justify_content_center_overflow_safe() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox =
+                Flexbox::from(vec![TextView::new("|Aardvark-Buffalo-Cat|").no_wrap()]);
+            flexbox.set_flex_shrink(0, 0);
+            flexbox.set_justify_content(JustifyContent::Center);
+            flexbox.set_justify_overflow(OverflowAlignment::Safe);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (15, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_align_content_center_overflow_unsafe() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|\n|Bat|\n|Cat|\n|Dog|\n|Elk|"),
+                TextView::new("|Fly|"),
+            ]);
+            flexbox.set_flex_wrap(FlexWrap::Wrap);
+            flexbox.set_align_content(AlignContent::Center);
+            flexbox.set_align_items(AlignItems::FlexStart);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (12, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_align_content_center_overflow_safe() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|\n|Bat|\n|Cat|\n|Dog|\n|Elk|"),
+                TextView::new("|Fly|"),
+            ]);
+            flexbox.set_flex_wrap(FlexWrap::Wrap);
+            flexbox.set_align_content(AlignContent::Center);
+            flexbox.set_align_items(AlignItems::FlexStart);
+            flexbox.set_align_overflow(OverflowAlignment::Safe);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (12, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_sizing_policy_fit_content() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|\n|Bat|"),
+                TextView::new("|Cat|"),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            flexbox.set_padding(Sides::symmetric(0, 1));
+            flexbox.set_sizing_policy(SizingPolicy::FitContent);
+            siv.add_layer(Panel::new(flexbox));
+        },
+        (30, 8),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_sizing_policy_fit_content_wrap() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+                TextView::new("|Dog|"),
+                TextView::new("|Elk|"),
+            ]);
+            flexbox.set_flex_wrap(FlexWrap::Wrap);
+            flexbox.set_main_axis_gap(1);
+            flexbox.set_cross_axis_gap(1);
+            flexbox.set_sizing_policy(SizingPolicy::FitContent);
+            siv.add_layer(Panel::new(flexbox));
+        },
+        (24, 8),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_sizing_policy_fit_content_zero_basis() {
+    // The flexbox fits the text of the item, not its flex-basis of 0.
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![TextView::new("|Aardvark & Bat|")]);
+            flexbox.set_flex_basis(0, FlexBasis::Zero);
+            flexbox.set_flex_grow(0, 1);
+            flexbox.set_sizing_policy(SizingPolicy::FitContent);
+            siv.add_layer(Panel::new(flexbox));
+        },
+        (24, 5),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_wrapping_text_cross_axis_size() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("The quick brown fox jumps over the lazy dog")),
+                Panel::new(TextView::new("|Ape|")),
+            ]);
+            flexbox.set_flex_shrink(1, 0);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (24, 7),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_cross_axis_gap_nowrap() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![TextView::new("|Ape|"), TextView::new("|Bat|")]);
+            flexbox.set_cross_axis_gap(2);
+            flexbox.set_align_items(AlignItems::FlexStart);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_align_content_stretch_panels() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+                Panel::new(TextView::new("Cat")),
+                Panel::new(TextView::new("Dog")),
+                Panel::new(TextView::new("Elk")),
+            ]);
+            flexbox.set_flex_wrap(FlexWrap::Wrap);
+            flexbox.set_align_content(AlignContent::Stretch);
+            flexbox.set_cross_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (16, 10),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_align_items_baseline() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("Name:").into_boxed_view(),
+                Panel::new(EditView::new().content("Gnu")).into_boxed_view(),
+                Button::new("Ok", |_| {}).into_boxed_view(),
+            ]);
+            flexbox.set_min_size(1, XY::new(Some(10), None));
+            flexbox.set_baseline(1, Some(1));
+            flexbox.set_main_axis_gap(1);
+            flexbox.set_align_items(AlignItems::Baseline);
+            flexbox.set_baseline_fallback(BaselineFallback::FirstRow);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (24, 4),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_align_items_baseline_named_view() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("Label:").into_boxed_view(),
+                TextView::new("first\nsecond")
+                    .with_name("text")
+                    .into_boxed_view(),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            flexbox.set_align_items(AlignItems::Baseline);
+            flexbox.set_baseline_fallback(BaselineFallback::FirstRow);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 3),
+    );
+    // The named view gets the same fallback as the label, so their first rows line up.
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_align_self_baseline_synthesized() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")).into_boxed_view(),
+                TextView::new("|Bat|\n|Bat|").into_boxed_view(),
+                TextView::new("|Cat|").into_boxed_view(),
+            ]);
+            flexbox.set_align_self(0, AlignSelf::Baseline);
+            flexbox.set_align_self(1, AlignSelf::Baseline);
+            flexbox.set_align_items(AlignItems::FlexEnd);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 6),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_grow_fractional() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+            ]);
+            flexbox.set_flex_basis(0, FlexBasis::Zero);
+            flexbox.set_flex_basis(1, FlexBasis::Zero);
+            flexbox.set_flex_grow(0, 1);
+            flexbox.set_flex_grow(1, 1.618);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (26, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_grow_sum_below_one() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+            ]);
+            flexbox.set_flex_grow(0, 0.25);
+            flexbox.set_flex_grow(1, 0.25);
+            flexbox.set_justify_content(JustifyContent::FlexEnd);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (30, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_grow_infinite() {
+    // An infinite factor is set to 0, so the item doesn't grow.
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+            ]);
+            flexbox.set_flex_grow(0, f64::INFINITY);
+            flexbox.set_flex_grow(1, 1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (26, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_grow_huge() {
+    // The sum of these factors overflows, they still share the free space equally.
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+            ]);
+            flexbox.set_flex_grow(0, 1e308);
+            flexbox.set_flex_grow(1, 1e308);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (26, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_flex_shrink_infinite() {
+    // An infinite factor is set to 0, so the item doesn't shrink.
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Aardvark|").no_wrap(),
+                TextView::new("|Bat|").no_wrap(),
+                TextView::new("|Chameleon|").no_wrap(),
+            ]);
+            flexbox.set_flex_shrink(0, f64::INFINITY);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_rounding_policy_center_biased() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("A")),
+                Panel::new(TextView::new("B")),
+                Panel::new(TextView::new("C")),
+                Panel::new(TextView::new("D")),
+            ]);
+            for index in 0..flexbox.len() {
+                flexbox.set_flex_basis(index, FlexBasis::Zero);
+                flexbox.set_flex_grow(index, 1);
+            }
+            flexbox.set_rounding_policy(RoundingPolicy::CenterBiased);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (22, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_rounding_policy_start_biased_space_between() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new("|Ape|"),
+                TextView::new("|Bat|"),
+                TextView::new("|Cat|"),
+                TextView::new("|Dog|"),
+            ]);
+            flexbox.set_justify_content(JustifyContent::SpaceBetween);
+            flexbox.set_rounding_policy(RoundingPolicy::StartBiased);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (25, 2),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_aspect_ratio_stretch() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Chart")),
+                Panel::new(TextView::new("Info")),
+            ]);
+            flexbox.set_aspect_ratio(0, Some(1.0));
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (30, 5),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_aspect_ratio_grow() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Chart")),
+                Panel::new(TextView::new("Info")),
+            ]);
+            flexbox.set_align_items(AlignItems::FlexStart);
+            flexbox.set_flex_grow(0, 1);
+            flexbox.set_aspect_ratio(0, Some(2.0));
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (30, 8),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_aspect_ratio_stretch_overflow() {
+    // Stretched to 8 rows, the chart would be 32 columns wide. It only takes what the info panel
+    // leaves instead.
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Chart")),
+                Panel::new(TextView::new("Info")),
+            ]);
+            flexbox.set_aspect_ratio(0, Some(2.0));
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (30, 8),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_position_absolute() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")).into_boxed_view(),
+                TextView::new("(3)").into_boxed_view(),
+                Panel::new(TextView::new("Bat")).into_boxed_view(),
+            ]);
+            for index in [0, 2] {
+                flexbox.set_flex_grow(index, 1);
+            }
+            flexbox.set_position(1, Position::Absolute);
+            flexbox.set_inset(
+                1,
+                Sides::new(Length::Cells(0), 1.into(), Length::Auto, Length::Auto),
+            );
+            flexbox.set_z_index(1, 1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_position_absolute_mouse() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let content_bat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Bat", move |_| content_bat.set_content("bat")).into_boxed_view(),
+                Button::new_raw("Ape", move |_| content_ape.set_content("ape")).into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            flexbox.set_position(0, Position::Absolute);
+            flexbox.set_inset(
+                0,
+                Sides::new(Length::Auto, Length::Auto, Length::Auto, 1.into()),
+            );
+            flexbox.set_z_index(0, 1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 1),
+    );
+    // "Bat" is drawn on top of "Ape", from the second column on, click on it.
+    tsiv.input(Event::Mouse {
+        offset: XY::new(0, 0),
+        position: XY::new(2, 0),
+        event: MouseEvent::Release(MouseButton::Left),
+    });
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_visibility_hidden() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+                Panel::new(TextView::new("Cat")),
+            ]);
+            flexbox.set_main_axis_gap(2);
+            flexbox.set_visible(1, Visibility::Hidden);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_visibility_collapsed() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+                Panel::new(TextView::new("Cat")),
+            ]);
+            flexbox.set_main_axis_gap(2);
+            flexbox.set_visible(1, Visibility::Collapsed);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 3),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_placement_center() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ok = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new("Ok", move |_| content_ok.set_content("ok")).into_boxed_view(),
+                Panel::new(TextView::new_with_content(content)).into_boxed_view(),
+            ]);
+            for index in 0..flexbox.len() {
+                flexbox.set_flex_grow(index, 1);
+            }
+            flexbox.set_placement(0, XY::new(Placement::Center, Placement::Center));
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 3),
+    );
+    // The button is centered in the left half, click on it.
+    tsiv.input(Event::Mouse {
+        offset: XY::new(0, 0),
+        position: XY::new(4, 1),
+        event: MouseEvent::Release(MouseButton::Left),
+    });
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_placement_end() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let mut flexbox = Flexbox::from(vec![
+                Panel::new(TextView::new("Ape")),
+                Panel::new(TextView::new("Bat")),
+            ]);
+            flexbox.set_flex_grow(0, 1);
+            flexbox.set_placement(0, XY::new(Placement::End, Placement::Stretch));
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 4),
+    );
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_focus_tab() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let content_bat = content.clone();
+            let content_cat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |_| content_ape.set_content("ape")).into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+                Button::new_raw("Bat", move |_| content_bat.set_content("bat")).into_boxed_view(),
+                Button::new_raw("Cat", move |_| content_cat.set_content("cat")).into_boxed_view(),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 1),
+    );
+    // The focus starts at "Ape", skip the text on the way to "Bat" and "Cat", then go back to
+    // "Bat".
+    tsiv.input(Event::Key(Key::Tab));
+    tsiv.input(Event::Key(Key::Tab));
+    tsiv.input(Event::Shift(Key::Tab));
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_focus_arrow_keys_row_reverse() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let content_bat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |_| content_ape.set_content("ape")).into_boxed_view(),
+                Button::new_raw("Bat", move |_| content_bat.set_content("bat")).into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            flexbox.set_flex_direction(FlexDirection::RowReverse);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 1),
+    );
+    // Items are placed as "none Bat Ape" at the right, so the focus starts at "Bat", to the left
+    // of "Ape".
+    tsiv.input(Event::Key(Key::Right));
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_focus_spatial() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let mut flexbox = Flexbox::new();
+            for name in ["Ape", "Bat", "Cat", "Dog", "Elk", "Fly"] {
+                let content = content.clone();
+                flexbox.push(Button::new_raw(name, move |_| {
+                    content.set_content(name.to_lowercase())
+                }));
+            }
+            flexbox.push(TextView::new_with_content(content));
+            flexbox.set_main_axis_gap(1);
+            flexbox.set_flex_wrap(FlexWrap::Wrap);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (11, 3),
+    );
+    // The focus starts at "Ape", move right to "Bat" and down to "Elk" below it.
+    tsiv.input(Event::Key(Key::Right));
+    tsiv.input(Event::Key(Key::Down));
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_focus_home_end_page_up() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let mut flexbox = Flexbox::new();
+            for name in ["Ape", "Bat", "Cat", "Dog", "Elk", "Fly"] {
+                let content = content.clone();
+                flexbox.push(Button::new_raw(name, move |_| {
+                    content.set_content(name.to_lowercase())
+                }));
+            }
+            flexbox.push(TextView::new_with_content(content));
+            flexbox.set_main_axis_gap(1);
+            flexbox.set_flex_wrap(FlexWrap::Wrap);
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (11, 3),
+    );
+    // The focus starts at "Ape", move down to "Dog", to the end of its line at "Fly", then up to
+    // the first line at "Cat" above it.
+    tsiv.input(Event::Key(Key::Down));
+    tsiv.input(Event::Key(Key::End));
+    tsiv.input(Event::Key(Key::PageUp));
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_empty() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            siv.add_fullscreen_layer(Flexbox::new());
+        },
+        (10, 1),
+    );
+    tsiv.input(Event::Key(Key::Tab));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_take_focus_cannot_focus() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let flexbox = Flexbox::from(vec![
+                TextView::new("Text"),
+                TextView::new_with_content(content),
+            ]);
+            siv.add_fullscreen_layer(LinearLayout::vertical().child(flexbox).child(
+                Button::new_raw("Ape", move |_| content_ape.set_content("ape")),
+            ));
+        },
+        (10, 2),
+    );
+    // The flexbox can't take the focus, so the button below it has it.
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_take_focus_leave_at_edge() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let content_bat = content.clone();
+            let content_cat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |_| content_ape.set_content("ape")),
+                Button::new_raw("Bat", move |_| content_bat.set_content("bat")),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(
+                LinearLayout::horizontal()
+                    .child(flexbox)
+                    .child(Button::new_raw("Cat", move |_| {
+                        content_cat.set_content("cat")
+                    }))
+                    .child(TextView::new_with_content(content)),
+            );
+        },
+        (20, 1),
+    );
+    // Move from "Ape" to "Bat", then out of the flexbox to "Cat", and back in at its last item.
+    tsiv.input(Event::Key(Key::Right));
+    tsiv.input(Event::Key(Key::Right));
+    tsiv.input(Event::Key(Key::Left));
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_remove_before_focused() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let content_bat = content.clone();
+            let content_cat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |_| content_ape.set_content("ape")).into_boxed_view(),
+                Button::new_raw("Bat", move |siv| {
+                    siv.call_on_name("flexbox", |flexbox: &mut Flexbox| {
+                        if flexbox.len() == 4 {
+                            flexbox.remove(0);
+                        }
+                    });
+                    content_bat.set_content("bat");
+                })
+                .into_boxed_view(),
+                Button::new_raw("Cat", move |_| content_cat.set_content("cat")).into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox.with_name("flexbox"));
+        },
+        (20, 1),
+    );
+    // Focus "Bat" and remove "Ape" with it, the focus stays on "Bat" instead of moving to "Cat".
+    tsiv.input(Event::Key(Key::Tab));
+    tsiv.input(Event::Key(Key::Enter));
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_remove_focused_last() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let content_bat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |_| content_ape.set_content("ape")).into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+                Button::new_raw("Bat", move |siv| {
+                    siv.call_on_name("flexbox", |flexbox: &mut Flexbox| flexbox.remove(2));
+                    content_bat.set_content("bat");
+                })
+                .into_boxed_view(),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox.with_name("flexbox"));
+        },
+        (20, 1),
+    );
+    // Focus "Bat" and remove it, the focus moves back past the text to "Ape".
+    tsiv.input(Event::Key(Key::Tab));
+    tsiv.input(Event::Key(Key::Enter));
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_hide_focused() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_bat = content.clone();
+            let content_cat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |siv| {
+                    siv.call_on_name("flexbox", |flexbox: &mut Flexbox| {
+                        flexbox.set_visible(0, Visibility::Hidden)
+                    });
+                })
+                .into_boxed_view(),
+                Button::new_raw("Bat", move |_| content_bat.set_content("bat")).into_boxed_view(),
+                Button::new_raw("Cat", move |_| content_cat.set_content("cat")).into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox.with_name("flexbox"));
+        },
+        (20, 1),
+    );
+    // Hide "Ape" while it has the focus, the focus moves on to "Bat".
+    tsiv.input(Event::Key(Key::Enter));
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_take_focus_hidden() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let content_bat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |_| content_ape.set_content("ape")).into_boxed_view(),
+                Button::new_raw("Bat", move |_| content_bat.set_content("bat")).into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+            ]);
+            flexbox.set_visible(0, Visibility::Hidden);
+            flexbox.set_main_axis_gap(1);
+            // Before the first layout, the hidden "Ape" doesn't get the focus.
+            assert!(flexbox.take_focus(Direction::front()).is_ok());
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 1),
+    );
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_take_focus_order() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let content_bat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |_| content_ape.set_content("ape")).into_boxed_view(),
+                Button::new_raw("Bat", move |_| content_bat.set_content("bat")).into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+            ]);
+            flexbox.set_order(0, 1);
+            flexbox.set_main_axis_gap(1);
+            // Before the first layout, "Bat" is the first item as well.
+            assert!(flexbox.take_focus(Direction::front()).is_ok());
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 1),
+    );
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_take_focus_row_reverse() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let content_bat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                TextView::new_with_content(content).into_boxed_view(),
+                Button::new_raw("Ape", move |_| content_ape.set_content("ape")).into_boxed_view(),
+                Button::new_raw("Bat", move |_| content_bat.set_content("bat")).into_boxed_view(),
+            ]);
+            flexbox.set_flex_direction(FlexDirection::RowReverse);
+            flexbox.set_main_axis_gap(1);
+            // Before the first layout, "Bat" is the leftmost item as well.
+            assert!(flexbox.take_focus(Direction::front()).is_ok());
+            siv.add_fullscreen_layer(flexbox);
+        },
+        (20, 1),
+    );
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_clear_focused() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |siv| {
+                    let content_bat = content_ape.clone();
+                    let content_cat = content_ape.clone();
+                    let content = content_ape.clone();
+                    siv.call_on_name("flexbox", |flexbox: &mut Flexbox| {
+                        flexbox.clear();
+                        flexbox.push(TextView::new_with_content(content).into_boxed_view());
+                        flexbox.push(
+                            Button::new_raw("Bat", move |_| content_bat.set_content("bat"))
+                                .into_boxed_view(),
+                        );
+                        flexbox.push(
+                            Button::new_raw("Cat", move |_| content_cat.set_content("cat"))
+                                .into_boxed_view(),
+                        );
+                    });
+                    content_ape.set_content("ape");
+                })
+                .into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox.with_name("flexbox"));
+        },
+        (20, 1),
+    );
+    // Clearing the flexbox drops the focus, so Enter doesn't reach any of the new items.
+    tsiv.input(Event::Key(Key::Enter));
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_clear_focus_new_items() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |siv| {
+                    let content_bat = content_ape.clone();
+                    let content_cat = content_ape.clone();
+                    let content = content_ape.clone();
+                    siv.call_on_name("flexbox", |flexbox: &mut Flexbox| {
+                        flexbox.clear();
+                        flexbox.push(TextView::new_with_content(content).into_boxed_view());
+                        flexbox.push(
+                            Button::new_raw("Bat", move |_| content_bat.set_content("bat"))
+                                .into_boxed_view(),
+                        );
+                        flexbox.push(
+                            Button::new_raw("Cat", move |_| content_cat.set_content("cat"))
+                                .into_boxed_view(),
+                        );
+                    });
+                    content_ape.set_content("ape");
+                })
+                .into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox.with_name("flexbox"));
+        },
+        (20, 1),
+    );
+    // After clearing the flexbox, the focus moves through the new items from the start.
+    tsiv.input(Event::Key(Key::Enter));
+    tsiv.input(Event::Key(Key::Tab));
+    tsiv.input(Event::Key(Key::Tab));
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn test_insert_before_focused() {
+    let mut tsiv = TestCursive::new_with_size(
+        |siv: &mut cursive::Cursive| {
+            let content = TextContent::new("none");
+            let content_ape = content.clone();
+            let content_bat = content.clone();
+            let mut flexbox = Flexbox::from(vec![
+                Button::new_raw("Ape", move |siv| {
+                    siv.call_on_name("flexbox", |flexbox: &mut Flexbox| {
+                        flexbox.insert(0, TextView::new("New"))
+                    });
+                    content_ape.set_content("ape");
+                })
+                .into_boxed_view(),
+                Button::new_raw("Bat", move |_| content_bat.set_content("bat")).into_boxed_view(),
+                TextView::new_with_content(content).into_boxed_view(),
+            ]);
+            flexbox.set_main_axis_gap(1);
+            siv.add_fullscreen_layer(flexbox.with_name("flexbox"));
+        },
+        (20, 1),
+    );
+    // Insert a text before "Ape" with it, the focus stays on "Ape" so the text is inserted twice.
+    tsiv.input(Event::Key(Key::Enter));
+    tsiv.input(Event::Key(Key::Enter));
+    assert_snapshot!(tsiv.last_screen());
+}